@@ -0,0 +1,208 @@
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+use loxone::events::{DaytimerEvents, EventTable, EventTableKind, TextEvents, ValueEvents, WeatherEvents};
+use loxone::loxapp3::{LoxoneDaytimerEntry, LoxoneState, LoxoneUUID, LoxoneWeatherEntry};
+
+const VALUE_EVENTS: u32 = 20_000;
+const TEXT_EVENTS: u32 = 5_000;
+const DAYTIMER_EVENTS: u32 = 1_000;
+const DAYTIMER_ENTRIES: i32 = 12;
+const WEATHER_EVENTS: u32 = 20;
+const WEATHER_ENTRIES: i32 = 168;
+
+fn write_uuid(buf: &mut Vec<u8>, i: u32) {
+    buf.write_u32::<LittleEndian>(0x1000_0000 + i).unwrap();
+    buf.write_u16::<LittleEndian>(0x02b4).unwrap();
+    buf.write_u16::<LittleEndian>(0x603c).unwrap();
+    buf.extend_from_slice(&[0xff, 0xff, 0xee, 0xe0, 0x00, 0xd8, 0x0c, 0xfd]);
+}
+
+fn value_table() -> Vec<u8> {
+    let mut buf = Vec::new();
+    for i in 0..VALUE_EVENTS {
+        write_uuid(&mut buf, i);
+        buf.write_f64::<LittleEndian>(f64::from(i) * 0.5).unwrap();
+    }
+    buf
+}
+
+fn text_table() -> Vec<u8> {
+    let mut buf = Vec::new();
+    for i in 0..TEXT_EVENTS {
+        let text = format!("Wohnzimmer Stehlampe {}", "x".repeat((i % 7) as usize));
+        write_uuid(&mut buf, i);
+        write_uuid(&mut buf, 0);
+        buf.write_u32::<LittleEndian>(text.len() as u32).unwrap();
+        buf.extend_from_slice(text.as_bytes());
+        buf.resize(buf.len() + (4 - text.len() % 4) % 4, 0);
+    }
+    buf
+}
+
+fn daytimer_table() -> Vec<u8> {
+    let mut buf = Vec::new();
+    for i in 0..DAYTIMER_EVENTS {
+        write_uuid(&mut buf, i);
+        buf.write_f64::<LittleEndian>(21.0).unwrap();
+        buf.write_i32::<LittleEndian>(DAYTIMER_ENTRIES).unwrap();
+        for j in 0..DAYTIMER_ENTRIES {
+            buf.write_i32::<LittleEndian>(j % 3).unwrap();
+            buf.write_i32::<LittleEndian>(j * 60).unwrap();
+            buf.write_i32::<LittleEndian>(j * 60 + 30).unwrap();
+            buf.write_i32::<LittleEndian>(0).unwrap();
+            buf.write_f64::<LittleEndian>(22.5).unwrap();
+        }
+    }
+    buf
+}
+
+fn weather_table() -> Vec<u8> {
+    let mut buf = Vec::new();
+    for i in 0..WEATHER_EVENTS {
+        write_uuid(&mut buf, i);
+        buf.write_u32::<LittleEndian>(370_000_000).unwrap();
+        buf.write_i32::<LittleEndian>(WEATHER_ENTRIES).unwrap();
+        for j in 0..WEATHER_ENTRIES {
+            for k in 0..5 {
+                buf.write_i32::<LittleEndian>(j + k).unwrap();
+            }
+            for k in 0..6 {
+                buf.write_f64::<LittleEndian>(f64::from(j) + f64::from(k)).unwrap();
+            }
+        }
+    }
+    buf
+}
+
+// Cursor based decoding as done before the event tables were decoded from borrowed slices.
+mod legacy {
+    use super::*;
+
+    fn parse_uuid(pack: &mut Cursor<Vec<u8>>) -> LoxoneUUID {
+        let d1 = pack.read_u32::<LittleEndian>().unwrap();
+        let d2 = pack.read_u16::<LittleEndian>().unwrap();
+        let d3 = pack.read_u16::<LittleEndian>().unwrap();
+        let mut d4 = [0; 8];
+        pack.read_exact(&mut d4).unwrap();
+        format!("{:08x}-{:04x}-{:04x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}", d1, d2, d3, d4[0], d4[1], d4[2], d4[3], d4[4], d4[5], d4[6], d4[7])
+    }
+
+    pub fn values(body: Vec<u8>) -> HashMap<LoxoneUUID, LoxoneState> {
+        let msg_len = body.len() as u64;
+        let mut pack = Cursor::new(body);
+        let mut events = Vec::new();
+        while pack.position() < msg_len {
+            let uuid = parse_uuid(&mut pack);
+            let val = pack.read_f64::<LittleEndian>().unwrap();
+            events.push((uuid, val));
+        }
+        events.into_iter().map(|(uuid, val)| (uuid, LoxoneState::Value(val))).collect()
+    }
+
+    pub fn texts(body: Vec<u8>) -> HashMap<LoxoneUUID, LoxoneState> {
+        let msg_len = body.len() as u64;
+        let mut pack = Cursor::new(body);
+        let mut events = Vec::new();
+        while pack.position() < msg_len {
+            let uuid = parse_uuid(&mut pack);
+            let uuid_icon = parse_uuid(&mut pack);
+            let text_len = pack.read_u32::<LittleEndian>().unwrap().try_into().unwrap();
+            let mut text_buf = vec![0; text_len];
+            pack.read_exact(&mut text_buf).unwrap();
+            let text = String::from_utf8(text_buf).unwrap();
+            events.push((uuid, uuid_icon, text));
+            if text_len % 4 != 0 {
+                pack.seek(SeekFrom::Current((4 - text_len % 4) as i64)).unwrap();
+            }
+        }
+        events.into_iter().map(|(uuid, uuid_icon, text)| (uuid, LoxoneState::Text(text, uuid_icon))).collect()
+    }
+
+    pub fn daytimers(body: Vec<u8>) -> HashMap<LoxoneUUID, LoxoneState> {
+        let msg_len = body.len() as u64;
+        let mut pack = Cursor::new(body);
+        let mut events = Vec::new();
+        while pack.position() < msg_len {
+            let uuid = parse_uuid(&mut pack);
+            let default_val = pack.read_f64::<LittleEndian>().unwrap();
+            let entries_len = pack.read_i32::<LittleEndian>().unwrap();
+            let mut entries = Vec::new();
+            for _ in 0..entries_len {
+                let mode = pack.read_i32::<LittleEndian>().unwrap();
+                let from = pack.read_i32::<LittleEndian>().unwrap();
+                let to = pack.read_i32::<LittleEndian>().unwrap();
+                let need_activate = pack.read_i32::<LittleEndian>().unwrap();
+                let value = pack.read_f64::<LittleEndian>().unwrap();
+                entries.push(LoxoneDaytimerEntry { mode, from, to, need_activate, value });
+            }
+            events.push((uuid, default_val, entries));
+        }
+        events.into_iter().map(|(uuid, default_val, entries)| (uuid, LoxoneState::Daytimer(entries, default_val))).collect()
+    }
+
+    pub fn weather(body: Vec<u8>) -> HashMap<LoxoneUUID, LoxoneState> {
+        let msg_len = body.len() as u64;
+        let mut pack = Cursor::new(body);
+        let mut events = Vec::new();
+        while pack.position() < msg_len {
+            let uuid = parse_uuid(&mut pack);
+            let last_update = pack.read_u32::<LittleEndian>().unwrap();
+            let entries_len = pack.read_i32::<LittleEndian>().unwrap();
+            let mut entries = Vec::new();
+            for _ in 0..entries_len {
+                entries.push(LoxoneWeatherEntry {
+                    timestamp: pack.read_i32::<LittleEndian>().unwrap(),
+                    weather_type: pack.read_i32::<LittleEndian>().unwrap(),
+                    wind_direction: pack.read_i32::<LittleEndian>().unwrap(),
+                    solar_radiation: pack.read_i32::<LittleEndian>().unwrap(),
+                    relative_humidity: pack.read_i32::<LittleEndian>().unwrap(),
+                    temperature: pack.read_f64::<LittleEndian>().unwrap(),
+                    perceived_temperature: pack.read_f64::<LittleEndian>().unwrap(),
+                    dew_point: pack.read_f64::<LittleEndian>().unwrap(),
+                    precipitation: pack.read_f64::<LittleEndian>().unwrap(),
+                    wind_speed: pack.read_f64::<LittleEndian>().unwrap(),
+                    barometric_pressure: pack.read_f64::<LittleEndian>().unwrap(),
+                });
+            }
+            events.push((uuid, last_update, entries));
+        }
+        events.into_iter().map(|(uuid, last_update, entries)| (uuid, LoxoneState::Weather(entries, last_update))).collect()
+    }
+}
+
+fn bench_table(c: &mut Criterion, name: &str, kind: EventTableKind, body: Vec<u8>, events: usize, legacy: fn(Vec<u8>) -> HashMap<LoxoneUUID, LoxoneState>, iterate: fn(&[u8]) -> usize) {
+    let table = EventTable::new(kind, body.clone());
+    assert_eq!(legacy(body.clone()), HashMap::from(&table));
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(events as u64));
+    group.bench_function("legacy", |b| b.iter_batched(|| body.clone(), |body| legacy(black_box(body)), BatchSize::LargeInput));
+    group.bench_function("iter", |b| b.iter(|| iterate(black_box(&body))));
+    group.bench_function("states", |b| b.iter(|| HashMap::from(black_box(&table))));
+    group.bench_function("into_iter", |b| b.iter_batched(|| table.clone(), |table| black_box(table).into_iter().map(black_box).count(), BatchSize::LargeInput));
+    group.finish();
+}
+
+fn value_events(c: &mut Criterion) {
+    bench_table(c, "value_events", EventTableKind::Value, value_table(), VALUE_EVENTS as usize, legacy::values, |buf| ValueEvents::new(buf).map(black_box).count());
+}
+
+fn text_events(c: &mut Criterion) {
+    bench_table(c, "text_events", EventTableKind::Text, text_table(), TEXT_EVENTS as usize, legacy::texts, |buf| TextEvents::new(buf).map(black_box).count());
+}
+
+fn daytimer_events(c: &mut Criterion) {
+    bench_table(c, "daytimer_events", EventTableKind::Daytimer, daytimer_table(), DAYTIMER_EVENTS as usize, legacy::daytimers, |buf| DaytimerEvents::new(buf).map(black_box).count());
+}
+
+fn weather_events(c: &mut Criterion) {
+    bench_table(c, "weather_events", EventTableKind::Weather, weather_table(), WEATHER_EVENTS as usize, legacy::weather, |buf| WeatherEvents::new(buf).map(black_box).count());
+}
+
+criterion_group!(benches, value_events, text_events, daytimer_events, weather_events);
+criterion_main!(benches);
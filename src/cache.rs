@@ -0,0 +1,72 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tokio::fs;
+
+const LOXAPP3_FILE: &str = "LoxAPP3.json";
+const LAST_MODIFIED_FILE: &str = "LoxAPP3.lastModified";
+
+/// On-disk cache for the LoxAPP3 structure file.
+///
+/// The structure file is stored along with the `LoxAPPversion3` value it was downloaded for.
+#[derive(Debug, Clone)]
+pub struct LoxAPP3Cache {
+    dir: PathBuf,
+}
+
+impl LoxAPP3Cache {
+    /// Creates a cache storing its files in the given directory.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the `lastModified` value of the cached structure file.
+    pub async fn last_modified(&self) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.dir.join(LAST_MODIFIED_FILE)).await {
+            Ok(last_modified) => Ok(Some(last_modified.trim().to_owned())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the cached structure file if it matches the given `last_modified` value.
+    pub async fn load(&self, last_modified: &str) -> io::Result<Option<String>> {
+        if self.last_modified().await?.as_deref() != Some(last_modified) {
+            return Ok(None)
+        }
+        match fs::read_to_string(self.dir.join(LOXAPP3_FILE)).await {
+            Ok(loxapp3) => Ok(Some(loxapp3)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Stores the given structure file along with its `last_modified` value.
+    pub async fn store(&self, last_modified: &str, loxapp3: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir).await?;
+        // Invalidate first so that an interrupted write is never mistaken for a valid cache entry.
+        match fs::remove_file(self.dir.join(LAST_MODIFIED_FILE)).await {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => (),
+        }
+        let tmp_path = self.dir.join(format!("{}.tmp", LOXAPP3_FILE));
+        fs::write(&tmp_path, loxapp3).await?;
+        fs::rename(&tmp_path, self.dir.join(LOXAPP3_FILE)).await?;
+        fs::write(self.dir.join(LAST_MODIFIED_FILE), last_modified).await
+    }
+
+    /// Removes the cached structure file.
+    pub async fn clear(&self) -> io::Result<()> {
+        for file in &[LAST_MODIFIED_FILE, LOXAPP3_FILE] {
+            match fs::remove_file(self.dir.join(file)).await {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
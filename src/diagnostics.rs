@@ -0,0 +1,124 @@
+//! Miniserver system information and diagnostics.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Firmware version reported by `jdev/cfg/version`, e.g. `12.0.2.24`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FirmwareVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub build: u32,
+}
+
+impl FromStr for FirmwareVersion {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('.');
+        let mut next = || parts.next().map_or(Ok(0), str::parse);
+        Ok(Self { major: next()?, minor: next()?, patch: next()?, build: next()? })
+    }
+}
+
+impl fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.major, self.minor, self.patch, self.build)
+    }
+}
+
+/// Network configuration of the Miniserver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkConfig {
+    pub ip: String,
+    pub mask: String,
+    pub gateway: String,
+    pub dns1: String,
+    pub dns2: String,
+    pub mac: String,
+    pub dhcp: bool,
+}
+
+/// Heap usage in kilobytes, as reported by `jdev/sys/heap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Heap {
+    pub used: u64,
+    pub total: Option<u64>,
+}
+
+impl Heap {
+    /// Parses heap usage formatted as `used/totalkB` or `used kB`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.splitn(2, '/');
+        let used = parse_counter(parts.next()?)?;
+        let total = match parts.next() {
+            Some(total) => Some(parse_counter(total)?),
+            None => None,
+        };
+        Some(Self { used, total })
+    }
+}
+
+/// Load of the Miniserver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemStatus {
+    /// Number of running tasks.
+    pub tasks: u64,
+    /// CPU load in percent.
+    pub cpu: f64,
+    pub heap: Heap,
+    pub context_switches: u64,
+}
+
+/// Packet counters of the LAN interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LanStatistics {
+    pub packets_sent: u64,
+    pub send_errors: u64,
+    pub packets_received: u64,
+}
+
+/// Packet counters of the CAN bus connecting the extensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusStatistics {
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    pub receive_errors: u64,
+    pub frame_errors: u64,
+    pub overruns: u64,
+}
+
+/// Explicit confirmation required to reboot the Miniserver.
+#[derive(Debug)]
+pub struct ConfirmReboot {
+    _private: (),
+}
+
+impl ConfirmReboot {
+    /// Confirms that the Miniserver, and with it the whole installation, will be unavailable while rebooting.
+    pub fn confirm() -> Self {
+        Self { _private: () }
+    }
+}
+
+/// Parses the leading integer of the given value, ignoring units such as `kB`.
+pub fn parse_counter(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let end = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    text[..end].parse().ok()
+}
+
+/// Parses a value in percent, with or without `%` sign.
+pub fn parse_percent(text: &str) -> Option<f64> {
+    text.trim().trim_end_matches('%').trim().parse().ok()
+}
+
+/// Parses a boolean value such as `1`, `true` or `on`.
+pub fn parse_flag(text: &str) -> Option<bool> {
+    match text.trim().to_lowercase().as_str() {
+        "1" | "true" | "on" => Some(true),
+        "0" | "false" | "off" => Some(false),
+        _ => None,
+    }
+}
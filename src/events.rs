@@ -0,0 +1,445 @@
+//! Zero-copy decoding of the binary event tables sent by the Miniserver.
+//!
+//! Event tables are decoded lazily from the borrowed frame body. Iterating over events does not
+//! allocate; UUIDs are only formatted and texts only copied when converting events into
+//! [`LoxoneState`] values.
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt;
+
+use crate::loxapp3::{LoxoneUUID, LoxoneState, LoxoneDaytimerEntry, LoxoneWeatherEntry};
+
+const UUID_LEN: usize = 16;
+const VALUE_EVENT_LEN: usize = UUID_LEN + 8;
+const TEXT_EVENT_HEADER_LEN: usize = UUID_LEN * 2 + 4;
+const DAYTIMER_EVENT_HEADER_LEN: usize = UUID_LEN + 8 + 4;
+const DAYTIMER_ENTRY_LEN: usize = 4 * 4 + 8;
+const WEATHER_EVENT_HEADER_LEN: usize = UUID_LEN + 4 + 4;
+const WEATHER_ENTRY_LEN: usize = 5 * 4 + 6 * 8;
+
+/// Kind of event table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTableKind {
+    Value,
+    Text,
+    Daytimer,
+    Weather,
+}
+
+/// Event table owning the raw frame body.
+#[derive(Debug, Clone)]
+pub struct EventTable {
+    kind: EventTableKind,
+    body: Vec<u8>,
+}
+
+/// UUID borrowed from an event table.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawUUID<'a>(&'a [u8; UUID_LEN]);
+
+/// Value state event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueEvent<'a> {
+    pub uuid: RawUUID<'a>,
+    pub value: f64,
+}
+
+/// Text state event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEvent<'a> {
+    pub uuid: RawUUID<'a>,
+    pub uuid_icon: RawUUID<'a>,
+    pub text: Cow<'a, str>,
+}
+
+/// Day timer state event.
+#[derive(Debug, Clone)]
+pub struct DaytimerEvent<'a> {
+    pub uuid: RawUUID<'a>,
+    pub default_value: f64,
+    pub entries: DaytimerEntries<'a>,
+}
+
+/// Weather state event.
+#[derive(Debug, Clone)]
+pub struct WeatherEvent<'a> {
+    pub uuid: RawUUID<'a>,
+    pub last_update: u32,
+    pub entries: WeatherEntries<'a>,
+}
+
+/// Iterator over the events of a value event table.
+#[derive(Debug, Clone)]
+pub struct ValueEvents<'a> {
+    buf: &'a [u8],
+}
+
+/// Iterator over the events of a text event table.
+#[derive(Debug, Clone)]
+pub struct TextEvents<'a> {
+    buf: &'a [u8],
+}
+
+/// Iterator over the events of a day timer event table.
+#[derive(Debug, Clone)]
+pub struct DaytimerEvents<'a> {
+    buf: &'a [u8],
+}
+
+/// Iterator over the events of a weather event table.
+#[derive(Debug, Clone)]
+pub struct WeatherEvents<'a> {
+    buf: &'a [u8],
+}
+
+/// Iterator over the entries of a day timer event.
+#[derive(Debug, Clone)]
+pub struct DaytimerEntries<'a> {
+    buf: &'a [u8],
+}
+
+/// Iterator over the entries of a weather event.
+#[derive(Debug, Clone)]
+pub struct WeatherEntries<'a> {
+    buf: &'a [u8],
+}
+
+/// Iterator over the state changes of an event table.
+#[derive(Debug, Clone)]
+pub enum States<'a> {
+    Value(ValueEvents<'a>),
+    Text(TextEvents<'a>),
+    Daytimer(DaytimerEvents<'a>),
+    Weather(WeatherEvents<'a>),
+}
+
+/// Iterator over the state changes of an owned event table.
+#[derive(Debug, Clone)]
+pub struct IntoStates {
+    table: EventTable,
+    pos: usize,
+}
+
+impl EventTable {
+    /// Wraps the given frame `body` without copying it.
+    pub fn new(kind: EventTableKind, body: Vec<u8>) -> Self {
+        Self { kind, body }
+    }
+
+    pub fn kind(&self) -> EventTableKind {
+        self.kind
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Returns an iterator over the state changes contained in the table.
+    pub fn states(&self) -> States<'_> {
+        States::new(self.kind, &self.body)
+    }
+}
+
+impl IntoIterator for EventTable {
+    type Item = (LoxoneUUID, LoxoneState);
+    type IntoIter = IntoStates;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoStates { table: self, pos: 0 }
+    }
+}
+
+impl From<&EventTable> for HashMap<LoxoneUUID, LoxoneState> {
+    fn from(event_table: &EventTable) -> Self {
+        event_table.states().collect()
+    }
+}
+
+impl<'a> RawUUID<'a> {
+    fn parse(buf: &'a [u8]) -> (Self, &'a [u8]) {
+        let (uuid, rest) = buf.split_at(UUID_LEN);
+        (Self(uuid.try_into().unwrap()), rest)
+    }
+
+    /// Returns the UUID in its textual representation.
+    pub fn to_uuid(&self) -> LoxoneUUID {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        let b = self.0;
+        // data1, data2 and data3 are little-endian, data4 is a plain byte array.
+        let bytes = [b[3], b[2], b[1], b[0], b[5], b[4], b[7], b[6], b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15]];
+        let mut uuid = String::with_capacity(35);
+        for (i, byte) in bytes.iter().enumerate() {
+            if i == 4 || i == 6 || i == 8 {
+                uuid.push('-');
+            }
+            uuid.push(HEX[(byte >> 4) as usize] as char);
+            uuid.push(HEX[(byte & 0x0f) as usize] as char);
+        }
+        uuid
+    }
+}
+
+impl fmt::Display for RawUUID<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_uuid())
+    }
+}
+
+impl fmt::Debug for RawUUID<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RawUUID({})", self)
+    }
+}
+
+impl PartialEq<str> for RawUUID<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.to_uuid() == other
+    }
+}
+
+impl<'a> ValueEvents<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+}
+
+impl<'a> Iterator for ValueEvents<'a> {
+    type Item = ValueEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.len() < VALUE_EVENT_LEN {
+            return None;
+        }
+        let (uuid, rest) = RawUUID::parse(self.buf);
+        let value = LittleEndian::read_f64(rest);
+        self.buf = &rest[8..];
+        Some(ValueEvent { uuid, value })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buf.len() / VALUE_EVENT_LEN;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ValueEvents<'_> {}
+
+impl<'a> TextEvents<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+}
+
+impl<'a> Iterator for TextEvents<'a> {
+    type Item = TextEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.len() < TEXT_EVENT_HEADER_LEN {
+            return None;
+        }
+        let (uuid, rest) = RawUUID::parse(self.buf);
+        let (uuid_icon, rest) = RawUUID::parse(rest);
+        let text_len = LittleEndian::read_u32(rest) as usize;
+        let rest = &rest[4..];
+        if rest.len() < text_len {
+            self.buf = &[];
+            return None;
+        }
+        let text = String::from_utf8_lossy(&rest[..text_len]);
+        // Texts are padded to a multiple of 4 bytes.
+        let padded_len = (text_len + 3) & !3;
+        self.buf = &rest[padded_len.min(rest.len())..];
+        Some(TextEvent { uuid, uuid_icon, text })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Events have a variable length, the header gives an upper bound.
+        (0, Some(self.buf.len() / TEXT_EVENT_HEADER_LEN))
+    }
+}
+
+impl<'a> DaytimerEvents<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+}
+
+impl<'a> Iterator for DaytimerEvents<'a> {
+    type Item = DaytimerEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.len() < DAYTIMER_EVENT_HEADER_LEN {
+            return None;
+        }
+        let (uuid, rest) = RawUUID::parse(self.buf);
+        let default_value = LittleEndian::read_f64(rest);
+        let entries_len = LittleEndian::read_i32(&rest[8..]).max(0) as usize * DAYTIMER_ENTRY_LEN;
+        let rest = &rest[12..];
+        if rest.len() < entries_len {
+            self.buf = &[];
+            return None;
+        }
+        let (entries, rest) = rest.split_at(entries_len);
+        self.buf = rest;
+        Some(DaytimerEvent { uuid, default_value, entries: DaytimerEntries { buf: entries } })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Events have a variable length, the header gives an upper bound.
+        (0, Some(self.buf.len() / DAYTIMER_EVENT_HEADER_LEN))
+    }
+}
+
+impl<'a> WeatherEvents<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+}
+
+impl<'a> Iterator for WeatherEvents<'a> {
+    type Item = WeatherEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.len() < WEATHER_EVENT_HEADER_LEN {
+            return None;
+        }
+        let (uuid, rest) = RawUUID::parse(self.buf);
+        let last_update = LittleEndian::read_u32(rest);
+        let entries_len = LittleEndian::read_i32(&rest[4..]).max(0) as usize * WEATHER_ENTRY_LEN;
+        let rest = &rest[8..];
+        if rest.len() < entries_len {
+            self.buf = &[];
+            return None;
+        }
+        let (entries, rest) = rest.split_at(entries_len);
+        self.buf = rest;
+        Some(WeatherEvent { uuid, last_update, entries: WeatherEntries { buf: entries } })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Events have a variable length, the header gives an upper bound.
+        (0, Some(self.buf.len() / WEATHER_EVENT_HEADER_LEN))
+    }
+}
+
+impl Iterator for DaytimerEntries<'_> {
+    type Item = LoxoneDaytimerEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.len() < DAYTIMER_ENTRY_LEN {
+            return None;
+        }
+        let (entry, rest) = self.buf.split_at(DAYTIMER_ENTRY_LEN);
+        self.buf = rest;
+        Some(LoxoneDaytimerEntry {
+            mode: LittleEndian::read_i32(&entry[0..]),
+            from: LittleEndian::read_i32(&entry[4..]),
+            to: LittleEndian::read_i32(&entry[8..]),
+            need_activate: LittleEndian::read_i32(&entry[12..]),
+            value: LittleEndian::read_f64(&entry[16..]),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buf.len() / DAYTIMER_ENTRY_LEN;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for DaytimerEntries<'_> {}
+
+impl Iterator for WeatherEntries<'_> {
+    type Item = LoxoneWeatherEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.len() < WEATHER_ENTRY_LEN {
+            return None;
+        }
+        let (entry, rest) = self.buf.split_at(WEATHER_ENTRY_LEN);
+        self.buf = rest;
+        Some(LoxoneWeatherEntry {
+            timestamp: LittleEndian::read_i32(&entry[0..]),
+            weather_type: LittleEndian::read_i32(&entry[4..]),
+            wind_direction: LittleEndian::read_i32(&entry[8..]),
+            solar_radiation: LittleEndian::read_i32(&entry[12..]),
+            relative_humidity: LittleEndian::read_i32(&entry[16..]),
+            temperature: LittleEndian::read_f64(&entry[20..]),
+            perceived_temperature: LittleEndian::read_f64(&entry[28..]),
+            dew_point: LittleEndian::read_f64(&entry[36..]),
+            precipitation: LittleEndian::read_f64(&entry[44..]),
+            wind_speed: LittleEndian::read_f64(&entry[52..]),
+            barometric_pressure: LittleEndian::read_f64(&entry[60..]),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buf.len() / WEATHER_ENTRY_LEN;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for WeatherEntries<'_> {}
+
+impl<'a> States<'a> {
+    fn new(kind: EventTableKind, buf: &'a [u8]) -> Self {
+        match kind {
+            EventTableKind::Value => Self::Value(ValueEvents::new(buf)),
+            EventTableKind::Text => Self::Text(TextEvents::new(buf)),
+            EventTableKind::Daytimer => Self::Daytimer(DaytimerEvents::new(buf)),
+            EventTableKind::Weather => Self::Weather(WeatherEvents::new(buf)),
+        }
+    }
+
+    /// Returns the number of bytes left to decode.
+    fn remaining(&self) -> usize {
+        match self {
+            Self::Value(events) => events.buf.len(),
+            Self::Text(events) => events.buf.len(),
+            Self::Daytimer(events) => events.buf.len(),
+            Self::Weather(events) => events.buf.len(),
+        }
+    }
+}
+
+impl Iterator for States<'_> {
+    type Item = (LoxoneUUID, LoxoneState);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Value(events) => events.next().map(|event| (event.uuid.to_uuid(), LoxoneState::Value(event.value))),
+            Self::Text(events) => events.next().map(|event| (event.uuid.to_uuid(), LoxoneState::Text(event.text.into_owned(), event.uuid_icon.to_uuid()))),
+            Self::Daytimer(events) => events.next().map(|event| (event.uuid.to_uuid(), LoxoneState::Daytimer(event.entries.collect(), event.default_value))),
+            Self::Weather(events) => events.next().map(|event| (event.uuid.to_uuid(), LoxoneState::Weather(event.entries.collect(), event.last_update))),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Value(events) => events.size_hint(),
+            Self::Text(events) => events.size_hint(),
+            Self::Daytimer(events) => events.size_hint(),
+            Self::Weather(events) => events.size_hint(),
+        }
+    }
+}
+
+impl Iterator for IntoStates {
+    type Item = (LoxoneUUID, LoxoneState);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Events are decoded one at a time from the owned body, resuming where the previous one ended.
+        let body = &self.table.body[self.pos..];
+        let mut states = States::new(self.table.kind, body);
+        let state = states.next();
+        self.pos += body.len() - states.remaining();
+        state
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        States::new(self.table.kind, &self.table.body[self.pos..]).size_hint()
+    }
+}
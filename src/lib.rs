@@ -1,14 +1,24 @@
 //! Rust implementation of the Loxone™ communication protocol (Web Socket).
 
+pub mod diagnostics;
+pub mod events;
 pub mod loxapp3;
+pub mod time;
 
+mod cache;
+mod watcher;
 mod ws;
 
+pub use crate::cache::LoxAPP3Cache;
+pub use crate::watcher::{StructureChanged, StructureWatcher};
 pub use crate::ws::WebSocket;
+pub use crate::ws::hash_visu_pwd;
 pub use crate::ws::EventReceiver;
 
 pub mod errors {
+    pub use crate::loxapp3::color::ParseColorError;
     pub use crate::ws::AuthenticationError;
+    pub use crate::ws::CommandError;
     pub use crate::ws::JwtRequestError;
     pub use crate::ws::KeyExchangeError;
     pub use crate::ws::LoxAPP3RequestError;
@@ -0,0 +1,152 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::loxapp3::commands::MutationBuilder;
+use crate::loxapp3::LoxoneApp3;
+
+/// Operating mode defined in the structure file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatingMode<'a> {
+    pub id: i8,
+    pub name: &'a str,
+}
+
+impl LoxoneApp3 {
+    /// Returns all operating modes, ordered by ID.
+    pub fn operating_mode_list(&self) -> Vec<OperatingMode<'_>> {
+        let mut modes: Vec<_> = self.operating_modes.iter().map(|(id, name)| OperatingMode { id: *id, name }).collect();
+        modes.sort_by_key(|mode| mode.id);
+        modes
+    }
+
+    /// Resolves the operating mode IDs of the `operatingMode` global state, ignoring unknown IDs.
+    pub fn active_operating_modes(&self, text: &str) -> Vec<OperatingMode<'_>> {
+        parse_operating_modes(text).into_iter().filter_map(|id| self.operating_modes.get(&id).map(|name| OperatingMode { id, name })).collect()
+    }
+}
+
+/// Parses the operating mode IDs of the `operatingMode` global state, given as a JSON array or comma-separated list.
+pub fn parse_operating_modes(text: &str) -> Vec<i8> {
+    let text = text.trim().trim_start_matches('[').trim_end_matches(']');
+    text.split(',').filter_map(|id| id.trim().parse::<f64>().ok()).map(|id| id as i8).collect()
+}
+
+/// How a calendar entry recurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum CalendarMode {
+    /// Every year on the start date.
+    Yearly,
+    /// Every year, relative to Easter Sunday.
+    Easter,
+    /// Once, from the start date.
+    Once,
+    /// Every year on a weekday of a month, e.g. the first Monday of May.
+    Weekday,
+    Unknown(u8),
+}
+
+impl From<u8> for CalendarMode {
+    fn from(val: u8) -> Self {
+        match val {
+            0 => Self::Yearly,
+            1 => Self::Easter,
+            2 => Self::Once,
+            3 => Self::Weekday,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<CalendarMode> for u8 {
+    fn from(mode: CalendarMode) -> Self {
+        match mode {
+            CalendarMode::Yearly => 0,
+            CalendarMode::Easter => 1,
+            CalendarMode::Once => 2,
+            CalendarMode::Weekday => 3,
+            CalendarMode::Unknown(val) => val,
+        }
+    }
+}
+
+/// Calendar entry activating an operating mode, as returned by `jdev/sps/calendargetentries`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEntry {
+    /// UUID of the entry, empty for entries that have not been created yet.
+    #[serde(default)]
+    pub uuid: String,
+    pub name: String,
+    pub operating_mode: i8,
+    pub cal_mode: CalendarMode,
+    /// Start date, formatted as `YYYY-MM-DD`.
+    ///
+    /// Required for all modes, only the year is relevant for [`CalendarMode::Easter`] entries.
+    pub start_date: String,
+    #[serde(default)]
+    pub start_hour: u8,
+    #[serde(default)]
+    pub start_minutes: u8,
+    #[serde(default)]
+    pub end_hour: u8,
+    #[serde(default)]
+    pub end_minutes: u8,
+    /// Days relative to Easter Sunday for [`CalendarMode::Easter`] entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eastern: Option<i32>,
+    /// Mode specific attribute, e.g. the weekday for [`CalendarMode::Weekday`] entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cal_mode_attr: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl CalendarEntry {
+    /// Parses the reply of `jdev/sps/calendargetentries`.
+    pub fn parse(text: &str) -> Result<Vec<Self>, serde_json::Error> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new())
+        }
+        serde_json::from_str(text)
+    }
+
+    /// Returns the start date, failing if it is not formatted as `YYYY-MM-DD`.
+    pub fn start_date(&self) -> Result<NaiveDate, chrono::ParseError> {
+        NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d")
+    }
+
+    /// Returns the command creating this entry, failing if the start date is invalid.
+    pub fn create_cmd(&self) -> Result<String, chrono::ParseError> {
+        self.args(MutationBuilder::new("jdev/sps/calendarcreateentry"))
+    }
+
+    /// Returns the command updating the entry with the same UUID, failing if the start date is invalid.
+    pub fn update_cmd(&self) -> Result<String, chrono::ParseError> {
+        self.args(MutationBuilder::new("jdev/sps/calendarupdateentry").arg(&self.uuid))
+    }
+
+    /// Returns the command deleting the entry with the given UUID.
+    pub fn delete_cmd(uuid: &str) -> String {
+        MutationBuilder::new("jdev/sps/calendardeleteentry").arg(uuid).build()
+    }
+
+    fn args(&self, builder: MutationBuilder) -> Result<String, chrono::ParseError> {
+        let start_date = self.start_date()?;
+        let builder = builder
+            .arg(&self.name)
+            .arg(self.operating_mode)
+            .arg(u8::from(self.cal_mode))
+            .arg(start_date.format("%Y-%m-%d"))
+            .arg(self.start_hour)
+            .arg(self.start_minutes)
+            .arg(self.end_hour)
+            .arg(self.end_minutes);
+        let builder = match (self.cal_mode, self.eastern, self.cal_mode_attr) {
+            (CalendarMode::Easter, Some(eastern), _) => builder.arg(eastern),
+            (_, _, Some(attr)) => builder.arg(attr),
+            _ => builder,
+        };
+        Ok(builder.build())
+    }
+}
@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// Color used by `ColorPicker` and `ColorPickerV2` controls, formatted as `hsv(h,s,v)` or `temp(b,k)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum LoxoneColor {
+    /// Hue in degrees, saturation and value in percent.
+    Hsv { hue: u16, saturation: u8, value: u8 },
+    /// Brightness in percent and white color temperature in kelvin.
+    Temp { brightness: u8, kelvin: u16 },
+}
+
+/// Sequence of colors played by a `ColorPickerV2` control.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LoxoneColorSequence {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub colors: Vec<LoxoneColor>,
+    /// Seconds between two colors.
+    pub interval: u32,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Error, Debug)]
+pub enum ParseColorError {
+    #[error("invalid color {0:?}")]
+    InvalidColor(String),
+    #[error("invalid json")]
+    JsonDeserialize(#[from] serde_json::Error),
+}
+
+impl LoxoneColor {
+    /// Lowest color temperature supported by the Miniserver.
+    pub const MIN_KELVIN: u16 = 2700;
+    /// Highest color temperature supported by the Miniserver.
+    pub const MAX_KELVIN: u16 = 6500;
+
+    /// Converts the given RGB color.
+    pub fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+        let (r, g, b) = (f64::from(red) / 255.0, f64::from(green) / 255.0, f64::from(blue) / 255.0);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+        Self::Hsv { hue: hue.round() as u16 % 360, saturation: (saturation * 100.0).round() as u8, value: (max * 100.0).round() as u8 }
+    }
+
+    /// Creates a white color with the given brightness in percent and temperature in kelvin, clamped to the supported range.
+    pub fn from_kelvin(brightness: u8, kelvin: u16) -> Self {
+        Self::Temp { brightness: brightness.min(100), kelvin: kelvin.clamp(Self::MIN_KELVIN, Self::MAX_KELVIN) }
+    }
+
+    /// Returns the color temperature in kelvin, `None` for HSV colors.
+    pub fn kelvin(&self) -> Option<u16> {
+        match self {
+            Self::Hsv { .. } => None,
+            Self::Temp { kelvin, .. } => Some(*kelvin),
+        }
+    }
+
+    /// Converts the color to RGB, color temperatures are approximated.
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        match *self {
+            Self::Hsv { hue, saturation, value } => {
+                let (s, v) = (f64::from(saturation.min(100)) / 100.0, f64::from(value.min(100)) / 100.0);
+                let c = v * s;
+                let h = f64::from(hue % 360) / 60.0;
+                let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+                let (r, g, b) = match h as u8 {
+                    0 => (c, x, 0.0),
+                    1 => (x, c, 0.0),
+                    2 => (0.0, c, x),
+                    3 => (0.0, x, c),
+                    4 => (x, 0.0, c),
+                    _ => (c, 0.0, x),
+                };
+                let m = v - c;
+                (to_u8((r + m) * 255.0), to_u8((g + m) * 255.0), to_u8((b + m) * 255.0))
+            },
+            Self::Temp { brightness, kelvin } => {
+                let (r, g, b) = kelvin_to_rgb(kelvin);
+                let scale = f64::from(brightness.min(100)) / 100.0;
+                (to_u8(r * scale), to_u8(g * scale), to_u8(b * scale))
+            },
+        }
+    }
+
+    /// Parses the text of the `favColors` global state.
+    pub fn parse_favorites(text: &str) -> Result<Vec<Self>, ParseColorError> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new())
+        }
+        Ok(serde_json::from_str(text)?)
+    }
+}
+
+impl LoxoneColorSequence {
+    /// Parses the text of the `sequence` state.
+    pub fn parse(text: &str) -> Result<Self, ParseColorError> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    /// Parses the text of the `favColorSequences` global state.
+    pub fn parse_favorites(text: &str) -> Result<Vec<Self>, ParseColorError> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new())
+        }
+        Ok(serde_json::from_str(text)?)
+    }
+}
+
+/// Approximates the RGB color of a black body at the given temperature.
+fn kelvin_to_rgb(kelvin: u16) -> (f64, f64, f64) {
+    let t = f64::from(kelvin) / 100.0;
+    let red = if t <= 66.0 { 255.0 } else { 329.698_727_446 * (t - 60.0).powf(-0.133_204_759_2) };
+    let green = if t <= 66.0 { 99.470_802_586_1 * t.ln() - 161.119_568_166_1 } else { 288.122_169_528_3 * (t - 60.0).powf(-0.075_514_849_2) };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.517_731_223_1 * (t - 10.0).ln() - 305.044_792_730_7
+    };
+    (red.clamp(0.0, 255.0), green.clamp(0.0, 255.0), blue.clamp(0.0, 255.0))
+}
+
+fn to_u8(val: f64) -> u8 {
+    val.round().clamp(0.0, 255.0) as u8
+}
+
+impl fmt::Display for LoxoneColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hsv { hue, saturation, value } => write!(f, "hsv({},{},{})", hue, saturation, value),
+            Self::Temp { brightness, kelvin } => write!(f, "temp({},{})", brightness, kelvin),
+        }
+    }
+}
+
+impl FromStr for LoxoneColor {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseColorError::InvalidColor(s.to_owned());
+        let s = s.trim();
+        let open = s.find('(').ok_or_else(invalid)?;
+        let args = s[open + 1..].strip_suffix(')').ok_or_else(invalid)?;
+        // Values may be sent as decimals, they are rounded to the precision used by the commands.
+        let args = args.split(',').map(|arg| arg.trim().parse::<f64>().map(f64::round)).collect::<Result<Vec<_>, _>>().map_err(|_| invalid())?;
+        match (&s[..open], args.as_slice()) {
+            ("hsv", [hue, saturation, value]) => Ok(Self::Hsv {
+                hue: hue.clamp(0.0, 360.0) as u16,
+                saturation: saturation.clamp(0.0, 100.0) as u8,
+                value: value.clamp(0.0, 100.0) as u8,
+            }),
+            ("temp", [brightness, kelvin]) => Ok(Self::Temp { brightness: brightness.clamp(0.0, 100.0) as u8, kelvin: kelvin.clamp(0.0, f64::from(u16::MAX)) as u16 }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl TryFrom<String> for LoxoneColor {
+    type Error = ParseColorError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<LoxoneColor> for String {
+    fn from(color: LoxoneColor) -> Self {
+        color.to_string()
+    }
+}
@@ -0,0 +1,618 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::fmt::{self, Write};
+
+use crate::loxapp3::color::{LoxoneColor, LoxoneColorSequence};
+use crate::loxapp3::controllers::{AudioZoneRepeat, ClimateControllerServiceMode, IRoomControllerV2Mode, IRoomControllerV2OperatingMode};
+use crate::loxapp3::daytimer::DaytimerEntry;
+use crate::loxapp3::query::LoxoneControlRef;
+use crate::loxapp3::tree::LoxoneControlNode;
+use crate::loxapp3::{LoxoneControl, LoxoneController, LoxoneMutation, LoxoneSubControl, LoxoneUUID};
+
+/// Characters encoded in command arguments, everything but the unreserved URI characters.
+const ARGUMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+/// Percent-encodes the given command argument so that it forms a single path segment.
+pub fn encode_argument(arg: &str) -> String {
+    utf8_percent_encode(arg, ARGUMENT).to_string()
+}
+
+/// Returns the given value if it is finite, NaN and infinities cannot be sent to controls.
+fn finite(value: f64) -> Option<f64> {
+    Some(value).filter(|value| value.is_finite())
+}
+
+/// Builder for mutations made of a command followed by `/`-separated arguments.
+///
+/// Arguments are percent-encoded so that text with spaces, slashes or non-ASCII characters reaches the control as a single argument.
+#[derive(Debug, Clone)]
+pub struct MutationBuilder {
+    mutation: LoxoneMutation,
+}
+
+impl MutationBuilder {
+    pub fn new(command: &str) -> Self {
+        Self { mutation: command.to_owned() }
+    }
+
+    /// Appends the given argument.
+    pub fn arg<T: fmt::Display>(mut self, arg: T) -> Self {
+        // Writing into a `String` never fails.
+        let _ = write!(self.mutation, "/{}", utf8_percent_encode(&arg.to_string(), ARGUMENT));
+        self
+    }
+
+    pub fn build(self) -> LoxoneMutation {
+        self.mutation
+    }
+}
+
+/// Command that can be sent to controls of specific controller types.
+pub trait Command {
+    /// Returns the mutation for the given controller, or `None` if the command does not apply to it.
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation>;
+}
+
+/// Control or sub-control that commands can be sent to.
+pub trait CommandTarget {
+    fn uuid(&self) -> &LoxoneUUID;
+    fn controller(&self) -> &LoxoneController;
+    /// Whether commands must be authorized by the visualization password.
+    fn is_secured(&self) -> bool;
+}
+
+impl CommandTarget for LoxoneControl {
+    fn uuid(&self) -> &LoxoneUUID {
+        &self.uuid_action
+    }
+
+    fn controller(&self) -> &LoxoneController {
+        &self.controller
+    }
+
+    fn is_secured(&self) -> bool {
+        self.is_secured
+    }
+}
+
+impl CommandTarget for LoxoneSubControl {
+    fn uuid(&self) -> &LoxoneUUID {
+        &self.uuid_action
+    }
+
+    fn controller(&self) -> &LoxoneController {
+        &self.controller
+    }
+
+    fn is_secured(&self) -> bool {
+        self.is_secured
+    }
+}
+
+impl<'a, T> CommandTarget for LoxoneControlRef<'a, T> {
+    fn uuid(&self) -> &LoxoneUUID {
+        self.uuid
+    }
+
+    fn controller(&self) -> &LoxoneController {
+        self.controller
+    }
+
+    fn is_secured(&self) -> bool {
+        self.is_secured
+    }
+}
+
+impl<'a> CommandTarget for LoxoneControlNode<'a> {
+    fn uuid(&self) -> &LoxoneUUID {
+        self.uuid
+    }
+
+    fn controller(&self) -> &LoxoneController {
+        self.controller
+    }
+
+    fn is_secured(&self) -> bool {
+        self.is_secured
+    }
+}
+
+/// Command for `Alarm` and `CentralAlarm` controls.
+///
+/// Alarms are usually secured, these commands are meant to be sent with [`WebSocket::send_secured_command`](crate::WebSocket::send_secured_command).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlarmCommand {
+    /// Arms the alarm, with or without the movement sensors.
+    On { movement: bool },
+    /// Arms the alarm after the configured delay, with or without the movement sensors.
+    DelayedOn { movement: bool },
+    /// Disarms the alarm.
+    Off,
+    /// Acknowledges the alarm.
+    Quit,
+    /// Disables or enables the movement sensors.
+    DisableMovement(bool),
+}
+
+impl Command for AlarmCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::Alarm(_) | LoxoneController::CentralAlarm(_) => Some(match self {
+                Self::On { movement } => format!("on/{}", u8::from(*movement)),
+                Self::DelayedOn { movement } => format!("delayedon/{}", u8::from(*movement)),
+                Self::Off => String::from("off"),
+                Self::Quit => String::from("quit"),
+                Self::DisableMovement(disabled) => format!("dismv/{}", u8::from(*disabled)),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `AudioZone` and `CentralAudioZone` controls.
+///
+/// Central audio zones only support starting and pausing playback.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioZoneCommand {
+    Play,
+    Pause,
+    /// Sets the volume in percent.
+    Volume(u8),
+    VolumeUp,
+    VolumeDown,
+    Previous,
+    Next,
+    Shuffle(bool),
+    Repeat(AudioZoneRepeat),
+    /// Plays the favorite with the given ID.
+    Favorite(u32),
+}
+
+impl Command for AudioZoneCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match (controller, self) {
+            (LoxoneController::CentralAudioZone(_), Self::Play) | (LoxoneController::CentralAudioZone(_), Self::Pause) | (LoxoneController::AudioZone(_), _) => Some(match self {
+                Self::Play => String::from("play"),
+                Self::Pause => String::from("pause"),
+                Self::Volume(volume) => format!("volume/{}", volume.min(&100)),
+                Self::VolumeUp => String::from("volUp"),
+                Self::VolumeDown => String::from("volDown"),
+                Self::Previous => String::from("prev"),
+                Self::Next => String::from("next"),
+                Self::Shuffle(shuffle) => format!("shuffle/{}", u8::from(*shuffle)),
+                Self::Repeat(repeat) => format!("repeat/{}", repeat.value()),
+                Self::Favorite(id) => format!("source/{}", id),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `CentralLightController` controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CentralLightControllerCommand {
+    /// Turns on all lights.
+    On,
+    /// Turns off all lights.
+    Off,
+}
+
+impl Command for CentralLightControllerCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::CentralLightController(_) => Some(match self {
+                Self::On => String::from("on"),
+                Self::Off => String::from("reset"),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `ClimateController` controls.
+///
+/// Temperatures that are not finite yield no mutation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClimateControllerCommand {
+    SetServiceMode(ClimateControllerServiceMode),
+    SetVentilation(bool),
+    /// Sets the outdoor temperature below which heating is allowed.
+    SetHeatingBoundary(f64),
+    /// Sets the outdoor temperature above which cooling is allowed.
+    SetCoolingBoundary(f64),
+}
+
+impl Command for ClimateControllerCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::ClimateController(_) => Some(match self {
+                Self::SetServiceMode(mode) => format!("setServiceMode/{}", u8::from(*mode)),
+                Self::SetVentilation(ventilation) => format!("setVentilation/{}", u8::from(*ventilation)),
+                Self::SetHeatingBoundary(temperature) => format!("setHeatingBoundary/{}", finite(*temperature)?),
+                Self::SetCoolingBoundary(temperature) => format!("setCoolingBoundary/{}", finite(*temperature)?),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `ColorPicker` and `ColorPickerV2` controls.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorPickerCommand {
+    /// Sets the color, hue in degrees, saturation and value in percent.
+    Hsv { hue: u16, saturation: u8, value: u8 },
+    /// Sets the white color temperature, brightness in percent and temperature in kelvin.
+    Temp { brightness: u8, kelvin: u16 },
+    /// Stores the given color as favorite at the given index.
+    SetFavorite { index: u8, color: LoxoneColor },
+    /// Plays the given color sequence, only applies to `ColorPickerV2` controls.
+    PlaySequence(LoxoneColorSequence),
+}
+
+impl From<LoxoneColor> for ColorPickerCommand {
+    fn from(color: LoxoneColor) -> Self {
+        match color {
+            LoxoneColor::Hsv { hue, saturation, value } => Self::Hsv { hue, saturation, value },
+            LoxoneColor::Temp { brightness, kelvin } => Self::Temp { brightness, kelvin },
+        }
+    }
+}
+
+impl Command for ColorPickerCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match (controller, self) {
+            (LoxoneController::ColorPicker(_), Self::PlaySequence(_)) => None,
+            (LoxoneController::ColorPicker(_), _) | (LoxoneController::ColorPickerV2(_), _) => Some(match self {
+                Self::Hsv { hue, saturation, value } => LoxoneColor::Hsv { hue: *hue.min(&360), saturation: *saturation.min(&100), value: *value.min(&100) }.to_string(),
+                Self::Temp { brightness, kelvin } => LoxoneColor::Temp { brightness: *brightness.min(&100), kelvin: *kelvin }.to_string(),
+                Self::SetFavorite { index, color } => format!("setFav/{}/{}", index, color),
+                Self::PlaySequence(sequence) => MutationBuilder::new("sequence").arg(serde_json::to_string(sequence).ok()?).build(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `Daytimer` and `IRCV2Daytimer` controls.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DaytimerCommand {
+    /// Replaces the schedule with the given entries, values are only sent for analog daytimers.
+    Set(Vec<DaytimerEntry>),
+    /// Sets the value used outside of the scheduled entries.
+    Default(f64),
+    /// Activates the entries that need activation.
+    Pulse,
+    /// Overrides the value for the given number of seconds.
+    StartOverride { value: f64, duration: u32 },
+    StopOverride,
+}
+
+impl Command for DaytimerCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        let analog = controller.daytimer_analog()?;
+        Some(match self {
+            Self::Set(entries) => {
+                let mut mutation = format!("set/{}", entries.len());
+                for entry in entries {
+                    let raw = entry.to_raw();
+                    // Writing into a `String` never fails.
+                    let _ = write!(mutation, "/{};{};{};{}", raw.mode, raw.from, raw.to, raw.need_activate);
+                    if analog {
+                        let _ = write!(mutation, ";{}", raw.value);
+                    }
+                }
+                mutation
+            },
+            Self::Default(value) => format!("default/{}", value),
+            Self::Pulse => String::from("pulse"),
+            Self::StartOverride { value, duration } => format!("startOverride/{}/{}", value, duration),
+            Self::StopOverride => String::from("stopOverride"),
+        })
+    }
+}
+
+/// Command for `Dimmer` controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DimmerCommand {
+    On,
+    Off,
+    /// Sets the position in percent, clamped to `0..=100` regardless of the configured dimmer range.
+    ///
+    /// Positions that are not finite yield no mutation.
+    Set(f64),
+}
+
+impl Command for DimmerCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::Dimmer(_) => Some(match self {
+                Self::On => String::from("on"),
+                Self::Off => String::from("off"),
+                Self::Set(position) => finite(*position)?.clamp(0.0, 100.0).to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `Gate` and `CentralGate` controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GateCommand {
+    Open,
+    Close,
+    Stop,
+    /// Opens the gate partially, only applies to single gates.
+    PartiallyOpen,
+}
+
+impl Command for GateCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match (controller, self) {
+            (LoxoneController::CentralGate(_), Self::PartiallyOpen) => None,
+            (LoxoneController::Gate(_), _) | (LoxoneController::CentralGate(_), _) => Some(match self {
+                Self::Open => String::from("open"),
+                Self::Close => String::from("close"),
+                Self::Stop => String::from("stop"),
+                Self::PartiallyOpen => String::from("partiallyOpen"),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `IRoomControllerV2` controls, temperatures in degrees of the configured unit.
+///
+/// Temperatures and offsets that are not finite yield no mutation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IRoomControllerV2Command {
+    OperatingMode(IRoomControllerV2OperatingMode),
+    SetComfortTemperature(f64),
+    SetComfortTemperatureCool(f64),
+    SetComfortTolerance(f64),
+    SetManualTemperature(f64),
+    /// Overrides the current mode for the given number of seconds, optionally with a target temperature.
+    StartOverride { mode: IRoomControllerV2Mode, duration: u32, temperature: Option<f64> },
+    StopOverride,
+    /// Starts the timer of the given mode for the given number of minutes.
+    StartTimer { mode: IRoomControllerV2Mode, minutes: u32 },
+    SetAbsentMinOffset(f64),
+    SetAbsentMaxOffset(f64),
+}
+
+impl Command for IRoomControllerV2Command {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::IRoomControllerV2(_) => Some(match self {
+                Self::OperatingMode(mode) => format!("operatingMode/{}", u8::from(*mode)),
+                Self::SetComfortTemperature(temperature) => format!("setComfortTemperature/{}", finite(*temperature)?),
+                Self::SetComfortTemperatureCool(temperature) => format!("setComfortTemperatureCool/{}", finite(*temperature)?),
+                Self::SetComfortTolerance(tolerance) => format!("setComfortTolerance/{}", finite(*tolerance)?),
+                Self::SetManualTemperature(temperature) => format!("setManualTemperature/{}", finite(*temperature)?),
+                Self::StartOverride { mode, duration, temperature: Some(temperature) } => format!("override/{}/{}/{}", u8::from(*mode), duration, finite(*temperature)?),
+                Self::StartOverride { mode, duration, temperature: None } => format!("override/{}/{}", u8::from(*mode), duration),
+                Self::StopOverride => String::from("stopOverride"),
+                Self::StartTimer { mode, minutes } => format!("startTimer/{}/{}", u8::from(*mode), minutes),
+                Self::SetAbsentMinOffset(offset) => format!("setAbsentMinOffset/{}", finite(*offset)?),
+                Self::SetAbsentMaxOffset(offset) => format!("setAbsentMaxOffset/{}", finite(*offset)?),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `Jalousie` and `CentralJalousie` controls.
+///
+/// Manual positions only apply to single jalousies, use a central fan-out to position all of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JalousieCommand {
+    /// Starts moving up, until [`UpOff`](JalousieCommand::UpOff) is sent.
+    Up,
+    UpOff,
+    /// Starts moving down, until [`DownOff`](JalousieCommand::DownOff) is sent.
+    Down,
+    DownOff,
+    FullUp,
+    FullDown,
+    /// Moves to the shading position.
+    Shade,
+    /// Enables automatic shading.
+    Auto,
+    /// Disables automatic shading.
+    NoAuto,
+    /// Moves to the given position in percent, `0` is fully up.
+    ManualPosition(f64),
+    /// Tilts the slats to the given position in percent.
+    ManualLamelle(f64),
+    Stop,
+}
+
+impl JalousieCommand {
+    fn to_mutation(self) -> LoxoneMutation {
+        match self {
+            Self::Up => String::from("up"),
+            Self::UpOff => String::from("UpOff"),
+            Self::Down => String::from("down"),
+            Self::DownOff => String::from("DownOff"),
+            Self::FullUp => String::from("FullUp"),
+            Self::FullDown => String::from("FullDown"),
+            Self::Shade => String::from("shade"),
+            Self::Auto => String::from("auto"),
+            Self::NoAuto => String::from("NoAuto"),
+            Self::ManualPosition(position) => MutationBuilder::new("manualPosition").arg(position.clamp(0.0, 100.0)).build(),
+            Self::ManualLamelle(position) => MutationBuilder::new("manualLamelle").arg(position.clamp(0.0, 100.0)).build(),
+            Self::Stop => String::from("stop"),
+        }
+    }
+}
+
+impl Command for JalousieCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match (controller, self) {
+            (LoxoneController::CentralJalousie(_), Self::ManualPosition(_)) | (LoxoneController::CentralJalousie(_), Self::ManualLamelle(_)) => None,
+            (LoxoneController::Jalousie(_), _) | (LoxoneController::CentralJalousie(_), _) => Some(self.to_mutation()),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `LightControllerV2` controls.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LightControllerV2Command {
+    AddMood(u8),
+    AddToFavoriteMood(u8),
+    ChangeTo(u8),
+    Learn { mood_id: u8, mood_name: String },
+    Minus,
+    MoveAdditionalMood { mood_id: u8, index: u8 },
+    MoveFavoriteMood { mood_id: u8, index: u8 },
+    MoveMood { mood_id: u8, index: u8 },
+    Plus,
+    Remove(u8),
+    RemoveFromFavoriteMood(u8),
+    RemoveMood(u8),
+}
+
+impl Command for LightControllerV2Command {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::LightControllerV2(_) => Some(match self {
+                Self::AddMood(mood_id) => format!("addMood/{}", mood_id),
+                Self::AddToFavoriteMood(mood_id) => format!("addToFavoriteMood/{}", mood_id),
+                Self::ChangeTo(mood_id) => format!("changeTo/{}", mood_id),
+                Self::Learn { mood_id, mood_name } => MutationBuilder::new("learn").arg(mood_id).arg(mood_name).build(),
+                Self::Minus => String::from("minus"),
+                Self::MoveAdditionalMood { mood_id, index } => format!("moveAdditionalMood/{}/{}", mood_id, index),
+                Self::MoveFavoriteMood { mood_id, index } => format!("moveFavoriteMood/{}/{}", mood_id, index),
+                Self::MoveMood { mood_id, index } => format!("moveMood/{}/{}", mood_id, index),
+                Self::Plus => String::from("plus"),
+                Self::Remove(mood_id) => format!("delete/{}", mood_id),
+                Self::RemoveFromFavoriteMood(mood_id) => format!("removeFromFavoriteMood/{}", mood_id),
+                Self::RemoveMood(mood_id) => format!("removeMood/{}", mood_id),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `Slider` controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SliderCommand {
+    Minus,
+    Plus,
+    /// Sets the value, clamped to the slider range and rounded to its step.
+    Set(f64),
+}
+
+impl Command for SliderCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::Slider(slider) => Some(match self {
+                Self::Minus => String::from("minus"),
+                Self::Plus => String::from("plus"),
+                Self::Set(value) => {
+                    let details = &slider.details;
+                    let value = if details.step > 0.0 { details.min + ((value - details.min) / details.step).round() * details.step } else { *value };
+                    // Drop the floating point noise introduced by the step arithmetic.
+                    ((value * 1e9).round() / 1e9).max(details.min).min(details.max).to_string()
+                },
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `SmokeAlarm` and `WaterAlarm` controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SmokeWaterAlarmCommand {
+    /// Acknowledges the alarm.
+    Confirm,
+    /// Mutes the acoustic alarm.
+    Mute,
+    /// Enables the service mode for the given number of seconds, `0` disables it.
+    ServiceMode(u32),
+}
+
+impl Command for SmokeWaterAlarmCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::SmokeAlarm(_) | LoxoneController::WaterAlarm(_) => Some(match self {
+                Self::Confirm => String::from("confirm"),
+                Self::Mute => String::from("mute"),
+                Self::ServiceMode(seconds) => format!("servicemode/{}", seconds),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `Switch` controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SwitchCommand {
+    On,
+    Off,
+    Pulse,
+}
+
+impl Command for SwitchCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::Switch(_) => Some(match self {
+                Self::On => String::from("on"),
+                Self::Off => String::from("off"),
+                Self::Pulse => String::from("pulse"),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `TextInput` controls.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextInputCommand {
+    /// Sets the text.
+    Set(String),
+}
+
+impl Command for TextInputCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::TextInput(_) => Some(match self {
+                Self::Set(text) => encode_argument(text),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Command for `Window` controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowCommand {
+    /// Starts opening, until [`OpenOff`](WindowCommand::OpenOff) is sent.
+    Open,
+    OpenOff,
+    /// Starts closing, until [`CloseOff`](WindowCommand::CloseOff) is sent.
+    Close,
+    CloseOff,
+    FullOpen,
+    FullClose,
+    /// Moves to the given position in percent, `0` is closed.
+    MoveToPosition(f64),
+    Stop,
+}
+
+impl Command for WindowCommand {
+    fn mutation(&self, controller: &LoxoneController) -> Option<LoxoneMutation> {
+        match controller {
+            LoxoneController::Window(_) => Some(match self {
+                Self::Open => String::from("open/on"),
+                Self::OpenOff => String::from("open/off"),
+                Self::Close => String::from("close/on"),
+                Self::CloseOff => String::from("close/off"),
+                Self::FullOpen => String::from("fullopen"),
+                Self::FullClose => String::from("fullclose"),
+                Self::MoveToPosition(position) => MutationBuilder::new("moveToPosition").arg(position.clamp(0.0, 100.0)).build(),
+                Self::Stop => String::from("stop"),
+            }),
+            _ => None,
+        }
+    }
+}
@@ -1,36 +1,314 @@
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
-use crate::loxapp3::{LoxoneUUID, LoxoneMutation, LoxoneSubControl};
+use crate::loxapp3::commands::MutationBuilder;
+use crate::loxapp3::format::format_value;
+use crate::loxapp3::{is_false, LoxoneUUID, LoxoneMutation, LoxoneSubControl};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Alarm {
+    pub details: AlarmDetails,
+    pub states: AlarmStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlarmDetails {
+    /// Whether the control is used as an alert rather than a burglar alarm.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alert: Option<bool>,
+    /// Whether presence detectors are connected, which enables arming with or without movement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_connected: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlarmStates {
+    pub armed: LoxoneUUID,
+    pub next_level: LoxoneUUID,
+    pub next_level_delay: LoxoneUUID,
+    pub next_level_delay_total: LoxoneUUID,
+    pub level: LoxoneUUID,
+    pub start_time: LoxoneUUID,
+    /// Remaining seconds until a delayed arming takes effect.
+    pub armed_delay: LoxoneUUID,
+    pub armed_delay_total: LoxoneUUID,
+    pub sensors: LoxoneUUID,
+    pub disabled_move: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AudioZone {
+    pub details: AudioZoneDetails,
+    pub states: AudioZoneStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioZoneDetails {
+    /// UUID of the Music Server the zone belongs to.
+    pub server: LoxoneUUID,
+    #[serde(rename = "playerid")]
+    pub player_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_type: Option<u8>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioZoneStates {
+    pub server_state: LoxoneUUID,
+    /// Playback state, see [`AudioZonePlayState::from_value`].
+    pub play_state: LoxoneUUID,
+    pub client_state: LoxoneUUID,
+    pub power: LoxoneUUID,
+    pub volume: LoxoneUUID,
+    pub max_volume: LoxoneUUID,
+    pub volume_step: LoxoneUUID,
+    pub shuffle: LoxoneUUID,
+    /// Repeat mode, see [`AudioZoneRepeat::from_value`].
+    pub repeat: LoxoneUUID,
+    pub song_name: LoxoneUUID,
+    pub artist: LoxoneUUID,
+    pub album: LoxoneUUID,
+    /// URL of the cover art.
+    pub cover: LoxoneUUID,
+    pub duration: LoxoneUUID,
+    pub progress: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Playback state of an audio zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioZonePlayState {
+    Unknown,
+    Stopped,
+    Paused,
+    Playing,
+}
+
+impl AudioZonePlayState {
+    /// Parses the value of the `playState` state.
+    pub fn from_value(value: f64) -> Self {
+        match value as i8 {
+            0 => Self::Stopped,
+            1 => Self::Paused,
+            2 => Self::Playing,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Repeat mode of an audio zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioZoneRepeat {
+    Off,
+    /// Repeats the whole queue.
+    All,
+    /// Repeats the current track.
+    Track,
+}
+
+impl AudioZoneRepeat {
+    /// Parses the value of the `repeat` state.
+    pub fn from_value(value: f64) -> Self {
+        match value as u8 {
+            1 => Self::All,
+            3 => Self::Track,
+            _ => Self::Off,
+        }
+    }
+
+    /// Returns the value used by the `repeat` state and command.
+    pub fn value(self) -> u8 {
+        match self {
+            Self::Off => 0,
+            Self::All => 1,
+            Self::Track => 3,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralAlarm {
+    pub details: CentralAlarmDetails,
+    /// States of the central control, if any, without a dedicated model.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub states: serde_json::Map<String, serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralAlarmDetails {
+    pub controls: Vec<CentralAlarmControl>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralAlarmControl {
+    pub uuid: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralAudioZone {
+    pub details: CentralAudioZoneDetails,
+    /// States of the central control, if any, without a dedicated model.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub states: serde_json::Map<String, serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralAudioZoneDetails {
+    pub controls: Vec<CentralAudioZoneControl>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralAudioZoneControl {
+    pub uuid: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralGate {
+    pub details: CentralGateDetails,
+    /// States of the central control, if any, without a dedicated model.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub states: serde_json::Map<String, serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralGateDetails {
+    pub controls: Vec<CentralGateControl>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralGateControl {
+    pub uuid: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralJalousie {
+    pub details: CentralJalousieDetails,
+    /// States of the central control, if any, without a dedicated model.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub states: serde_json::Map<String, serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralJalousieDetails {
+    pub controls: Vec<CentralJalousieControl>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CentralJalousieControl {
+    pub uuid: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CentralLightController {
-    pub details: CentralLightControllerDetails
+    pub details: CentralLightControllerDetails,
+    /// States of the central control, if any, without a dedicated model.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub states: serde_json::Map<String, serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CentralLightControllerDetails {
     pub controls: Vec<CentralLightControllerControl>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CentralLightControllerControl {
     pub uuid: LoxoneUUID,
-    pub id: u8
+    pub id: u8,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
- pub struct ClimateController {
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ClimateController {
     pub details: ClimateControllerDetails,
     pub states: ClimateControllerStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ClimateControllerDetails {
-    pub capabilities: u8,
+    pub capabilities: ClimateControllerCapabilities,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Capabilities of a climate controller.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ClimateControllerCapabilities(pub u8);
+
+impl ClimateControllerCapabilities {
+    pub const HEATING: u8 = 1 << 0;
+    pub const COOLING: u8 = 1 << 1;
+    pub const VENTILATION: u8 = 1 << 2;
+
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub fn contains(&self, flag: u8) -> bool {
+        self.0 & flag == flag
+    }
+
+    pub fn can_heat(&self) -> bool {
+        self.contains(Self::HEATING)
+    }
+
+    pub fn can_cool(&self) -> bool {
+        self.contains(Self::COOLING)
+    }
+
+    pub fn can_ventilate(&self) -> bool {
+        self.contains(Self::VENTILATION)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClimateControllerStates {
     pub controls: LoxoneUUID,
@@ -47,126 +325,544 @@ pub struct ClimateControllerStates {
     pub service_mode: LoxoneUUID,
     pub next_maintenance: LoxoneUUID,
     pub ventilation: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Room controller managed by a climate controller, as listed by the `controls` state.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ClimateControllerControl {
+    pub uuid: LoxoneUUID,
+    /// `-1` for cooling, `1` for heating and `0` if the room has no demand.
+    #[serde(default)]
+    pub demand: i8,
+    #[serde(rename = "temp", skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Service mode reported by the `serviceMode` state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClimateControllerServiceMode {
+    Off,
+    /// Heating and cooling are turned off.
+    Standby,
+    HeatingOn,
+    CoolingOn,
+    VentilationOn,
+    Unknown(u8),
+}
+
+impl From<u8> for ClimateControllerServiceMode {
+    fn from(val: u8) -> Self {
+        match val {
+            0 => Self::Off,
+            1 => Self::Standby,
+            2 => Self::HeatingOn,
+            3 => Self::CoolingOn,
+            4 => Self::VentilationOn,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<ClimateControllerServiceMode> for u8 {
+    fn from(mode: ClimateControllerServiceMode) -> Self {
+        match mode {
+            ClimateControllerServiceMode::Off => 0,
+            ClimateControllerServiceMode::Standby => 1,
+            ClimateControllerServiceMode::HeatingOn => 2,
+            ClimateControllerServiceMode::CoolingOn => 3,
+            ClimateControllerServiceMode::VentilationOn => 4,
+            ClimateControllerServiceMode::Unknown(val) => val,
+        }
+    }
+}
+
+/// Reason reported by the `overwriteReason` state why the automatic mode is overridden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClimateControllerOverwriteReason {
+    None,
+    ServiceMode,
+    /// The outdoor temperature is beyond the heating or cooling boundary.
+    TemperatureBoundary,
+    /// A logic input of the block forces the mode.
+    External,
+    Unknown(u8),
 }
 
-#[derive(Debug, Deserialize)]
+impl From<u8> for ClimateControllerOverwriteReason {
+    fn from(val: u8) -> Self {
+        match val {
+            0 => Self::None,
+            1 => Self::ServiceMode,
+            2 => Self::TemperatureBoundary,
+            3 => Self::External,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl ClimateController {
+    /// Parses the text of the `controls` state.
+    pub fn controls(text: &str) -> Result<Vec<ClimateControllerControl>, serde_json::Error> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new())
+        }
+        serde_json::from_str(text)
+    }
+
+    /// Parses the value of the `serviceMode` state.
+    pub fn service_mode(value: f64) -> ClimateControllerServiceMode {
+        ClimateControllerServiceMode::from(value as u8)
+    }
+
+    /// Parses the value of the `overwriteReason` state.
+    pub fn overwrite_reason(value: f64) -> ClimateControllerOverwriteReason {
+        ClimateControllerOverwriteReason::from(value as u8)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ColorPicker {
     pub details: ColorPickerDetails,
     pub states: ColorPickerStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ColorPickerDetails {
     pub picker_type: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ColorPickerStates {
+    /// Current color, see [`LoxoneColor`](crate::loxapp3::color::LoxoneColor).
     pub color: LoxoneUUID,
     pub favorites: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ColorPickerV2 {
     pub states: ColorPickerV2States,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ColorPickerV2States {
+    /// Current color, see [`LoxoneColor`](crate::loxapp3::color::LoxoneColor).
     pub color: LoxoneUUID,
+    /// Color sequence as JSON, see [`LoxoneColorSequence`](crate::loxapp3::color::LoxoneColorSequence).
     pub sequence: LoxoneUUID,
     pub sequence_color_idx: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Daytimer {
+    pub details: DaytimerDetails,
+    pub states: DaytimerStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DaytimerDetails {
+    pub analog: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaytimerStates {
+    pub entries_and_default_value: LoxoneUUID,
+    pub mode: LoxoneUUID,
+    pub value: LoxoneUUID,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#override: Option<LoxoneUUID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub needs_activation: Option<LoxoneUUID>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Dimmer {
     pub states: DimmerStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct DimmerStates {
     pub position: LoxoneUUID,
     pub min: LoxoneUUID,
     pub max: LoxoneUUID,
     pub step: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Gate {
+    pub details: GateDetails,
+    pub states: GateStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GateDetails {
+    pub animation: GateAnimation,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Type of gate, used to pick the animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum GateAnimation {
+    GarageDoor,
+    SingleGateLeft,
+    SingleGateRight,
+    DoubleGate,
+    FoldingDoorLeft,
+    FoldingDoorRight,
+    Unknown(u8),
+}
+
+impl From<u8> for GateAnimation {
+    fn from(val: u8) -> Self {
+        match val {
+            0 => Self::GarageDoor,
+            1 => Self::SingleGateLeft,
+            2 => Self::SingleGateRight,
+            3 => Self::DoubleGate,
+            4 => Self::FoldingDoorLeft,
+            5 => Self::FoldingDoorRight,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<GateAnimation> for u8 {
+    fn from(animation: GateAnimation) -> Self {
+        match animation {
+            GateAnimation::GarageDoor => 0,
+            GateAnimation::SingleGateLeft => 1,
+            GateAnimation::SingleGateRight => 2,
+            GateAnimation::DoubleGate => 3,
+            GateAnimation::FoldingDoorLeft => 4,
+            GateAnimation::FoldingDoorRight => 5,
+            GateAnimation::Unknown(val) => val,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GateStates {
+    /// Position from `0` (closed) to `1` (open).
+    pub position: LoxoneUUID,
+    /// Movement, see [`Movement::from_value`].
+    pub active: LoxoneUUID,
+    pub prevent_open: LoxoneUUID,
+    pub prevent_close: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Movement of a gate or window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    Closing,
+    Stopped,
+    Opening,
+}
+
+impl Movement {
+    /// Parses the value of a gate `active` or window `direction` state.
+    pub fn from_value(value: f64) -> Self {
+        if value < 0.0 {
+            Self::Closing
+        } else if value > 0.0 {
+            Self::Opening
+        } else {
+            Self::Stopped
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct InfoOnlyAnalog {
     pub details: InfoOnlyAnalogDetails,
-    pub states: InfoOnlyStates
+    pub states: InfoOnlyStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct InfoOnlyDigital {
-    pub details: InfoOnlyAnalogDetails,
-    pub states: InfoOnlyStates
+    pub details: InfoOnlyDigitalDetails,
+    pub states: InfoOnlyStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct InfoOnlyAnalogDetails {
     pub format: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+impl InfoOnlyAnalogDetails {
+    /// Formats the given value, see [`format_value`].
+    pub fn display(&self, value: f64) -> String {
+        format_value(&self.format, value)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct InfoOnlyDigitalDetails {
-    pub text: u8,
-    pub image: LoxoneUUID,
-    pub color: u8
+    pub text: InfoOnlyDigitalValues,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<InfoOnlyDigitalValues>,
+    pub color: InfoOnlyDigitalValues,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl InfoOnlyDigitalDetails {
+    /// Returns the text and color configured for the given value.
+    pub fn display(&self, value: f64) -> (&str, &str) {
+        (self.text.get(value), self.color.get(value))
+    }
 }
 
-#[derive(Debug, Deserialize)]
+/// Pair of values shown when a digital value is on or off.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InfoOnlyDigitalValues {
+    pub on: String,
+    pub off: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl InfoOnlyDigitalValues {
+    pub fn get(&self, value: f64) -> &str {
+        if value != 0.0 { &self.on } else { &self.off }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct InfoOnlyStates {
     pub value: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct IRCV2Daytimer {
     pub details: IRCV2DaytimerDetails,
     pub states: IRCV2DaytimerStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct IRCV2DaytimerDetails {
     pub format: String,
-    pub analog: bool
+    pub analog: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+impl IRCV2DaytimerDetails {
+    /// Formats the given value, see [`format_value`].
+    pub fn display(&self, value: f64) -> String {
+        format_value(&self.format, value)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IRCV2DaytimerStates {
     pub entries_and_default_value: LoxoneUUID,
     pub mode: LoxoneUUID,
     pub mode_list: LoxoneUUID,
     pub value: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IRoomControllerV2 {
     pub details: IRoomControllerV2Details,
     pub states: IRoomControllerV2States,
     pub sub_controls: HashMap<LoxoneUUID, LoxoneSubControl>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IRoomControllerV2Details {
     pub format: String,
     pub timer_modes: Vec<IRoomControllerV2TimerMode>,
     pub connected_inputs: u32,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+impl IRoomControllerV2Details {
+    /// Formats the given temperature, see [`format_value`].
+    pub fn display(&self, value: f64) -> String {
+        format_value(&self.format, value)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct IRoomControllerV2TimerMode {
     pub id: u8,
     pub name: String,
-    pub description: String
+    pub description: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Mode reported by the `activeMode` state and used by timers and overrides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IRoomControllerV2Mode {
+    Economy,
+    ComfortTemperature,
+    BuildingProtection,
+    Manual,
+    Unknown(u8),
 }
 
-#[derive(Debug, Deserialize)]
+impl From<u8> for IRoomControllerV2Mode {
+    fn from(val: u8) -> Self {
+        match val {
+            0 => Self::Economy,
+            1 => Self::ComfortTemperature,
+            2 => Self::BuildingProtection,
+            3 => Self::Manual,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<IRoomControllerV2Mode> for u8 {
+    fn from(mode: IRoomControllerV2Mode) -> Self {
+        match mode {
+            IRoomControllerV2Mode::Economy => 0,
+            IRoomControllerV2Mode::ComfortTemperature => 1,
+            IRoomControllerV2Mode::BuildingProtection => 2,
+            IRoomControllerV2Mode::Manual => 3,
+            IRoomControllerV2Mode::Unknown(val) => val,
+        }
+    }
+}
+
+/// Operating mode reported by the `operatingMode` state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IRoomControllerV2OperatingMode {
+    Automatic,
+    AutomaticHeating,
+    AutomaticCooling,
+    Manual,
+    ManualHeating,
+    ManualCooling,
+    Unknown(u8),
+}
+
+impl From<u8> for IRoomControllerV2OperatingMode {
+    fn from(val: u8) -> Self {
+        match val {
+            0 => Self::Automatic,
+            1 => Self::AutomaticHeating,
+            2 => Self::AutomaticCooling,
+            3 => Self::Manual,
+            4 => Self::ManualHeating,
+            5 => Self::ManualCooling,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<IRoomControllerV2OperatingMode> for u8 {
+    fn from(mode: IRoomControllerV2OperatingMode) -> Self {
+        match mode {
+            IRoomControllerV2OperatingMode::Automatic => 0,
+            IRoomControllerV2OperatingMode::AutomaticHeating => 1,
+            IRoomControllerV2OperatingMode::AutomaticCooling => 2,
+            IRoomControllerV2OperatingMode::Manual => 3,
+            IRoomControllerV2OperatingMode::ManualHeating => 4,
+            IRoomControllerV2OperatingMode::ManualCooling => 5,
+            IRoomControllerV2OperatingMode::Unknown(val) => val,
+        }
+    }
+}
+
+/// Entry of the `overrideEntries` state.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IRoomControllerV2OverrideEntry {
+    /// End of the override, in seconds since 2009-01-01 Miniserver time.
+    pub end: u64,
+    #[serde(default)]
+    pub is_timer: bool,
+    #[serde(default)]
+    pub source: String,
+    /// Timer mode ID of the override, see [`IRoomControllerV2::timer_mode`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<u8>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IRoomControllerV2 {
+    /// Returns the timer mode with the given ID.
+    pub fn timer_mode(&self, id: u8) -> Option<&IRoomControllerV2TimerMode> {
+        self.details.timer_modes.iter().find(|mode| mode.id == id)
+    }
+
+    /// Parses the value of the `activeMode` state, along with the matching timer mode.
+    pub fn active_mode(&self, value: f64) -> (IRoomControllerV2Mode, Option<&IRoomControllerV2TimerMode>) {
+        let id = value as u8;
+        (IRoomControllerV2Mode::from(id), self.timer_mode(id))
+    }
+
+    /// Parses the value of the `operatingMode` state.
+    pub fn operating_mode(value: f64) -> IRoomControllerV2OperatingMode {
+        IRoomControllerV2OperatingMode::from(value as u8)
+    }
+
+    /// Parses the text of the `overrideEntries` state.
+    pub fn override_entries(text: &str) -> Result<Vec<IRoomControllerV2OverrideEntry>, serde_json::Error> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new())
+        }
+        serde_json::from_str(text)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IRoomControllerV2States {
     pub active_mode: LoxoneUUID,
@@ -184,93 +880,204 @@ pub struct IRoomControllerV2States {
     pub heat_protect_temperature: LoxoneUUID,
     pub comfort_temperature_offset: LoxoneUUID,
     pub open_window: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Jalousie {
+    pub details: JalousieDetails,
+    pub states: JalousieStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JalousieDetails {
+    pub animation: JalousieAnimation,
+    pub is_automatic: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Type of shading device, used to pick the animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum JalousieAnimation {
+    Blinds,
+    Shutters,
+    CurtainBothSides,
+    NotSupported,
+    CurtainLeft,
+    CurtainRight,
+    Awning,
+    Unknown(u8),
+}
+
+impl From<u8> for JalousieAnimation {
+    fn from(val: u8) -> Self {
+        match val {
+            0 => Self::Blinds,
+            1 => Self::Shutters,
+            2 => Self::CurtainBothSides,
+            3 => Self::NotSupported,
+            4 => Self::CurtainLeft,
+            5 => Self::CurtainRight,
+            6 => Self::Awning,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<JalousieAnimation> for u8 {
+    fn from(animation: JalousieAnimation) -> Self {
+        match animation {
+            JalousieAnimation::Blinds => 0,
+            JalousieAnimation::Shutters => 1,
+            JalousieAnimation::CurtainBothSides => 2,
+            JalousieAnimation::NotSupported => 3,
+            JalousieAnimation::CurtainLeft => 4,
+            JalousieAnimation::CurtainRight => 5,
+            JalousieAnimation::Awning => 6,
+            JalousieAnimation::Unknown(val) => val,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JalousieStates {
+    pub up: LoxoneUUID,
+    pub down: LoxoneUUID,
+    pub position: LoxoneUUID,
+    pub shade_position: LoxoneUUID,
+    pub safety_active: LoxoneUUID,
+    pub auto_allowed: LoxoneUUID,
+    pub auto_active: LoxoneUUID,
+    pub locked: LoxoneUUID,
+    pub info_text: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NfcCodeTouchDetails {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub access_output: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub place: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
     pub two_factor_auth: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct NfcCodeTouch {
     pub details: NfcCodeTouchDetails,
     pub states: NfcCodeTouchStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NfcCodeTouchStates {
     pub history_date: LoxoneUUID,
     pub code_date: LoxoneUUID,
     pub device_state: LoxoneUUID,
     pub nfc_learn_result: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LightControllerV2 {
     pub details: LightControllerV2Details,
     pub states: LightControllerV2States,
     pub sub_controls: HashMap<LoxoneUUID, LoxoneSubControl>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LightControllerV2Details {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub master_value: Option<LoxoneUUID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub master_color: Option<LoxoneUUID>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LightControllerV2States {
     pub active_moods: LoxoneUUID,
     pub mood_list: LoxoneUUID,
     pub favorite_moods: LoxoneUUID,
     pub additional_moods: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Slider {
     pub details: SliderDetails,
-    pub states: SliderStates
+    pub states: SliderStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SliderDetails {
     pub format: String,
-    pub min: f32,
-    pub max: f32,
-    pub step: f32,
+    pub min: f64,
+    pub max: f64,
+    pub step: f64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl SliderDetails {
+    /// Formats the given value, see [`format_value`].
+    pub fn display(&self, value: f64) -> String {
+        format_value(&self.format, value)
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SliderStates {
     pub value: LoxoneUUID,
     pub error: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SmokeWaterAlarm {
     pub details: SmokeWaterAlarmDetails,
     pub states: SmokeWaterAlarmStates,
     pub sub_controls: HashMap<LoxoneUUID, LoxoneSubControl>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SmokeWaterAlarmDetails {
     pub has_acoustic_alarm: bool,
     pub available_alarms: u8,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SmokeWaterAlarmStates {
     pub next_level: LoxoneUUID,
@@ -284,29 +1091,225 @@ pub struct SmokeWaterAlarmStates {
     pub start_time: LoxoneUUID,
     pub time_service_mode: LoxoneUUID,
     pub are_alarm_signals_off: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Switch {
-    pub states: SwitchStates
+    pub states: SwitchStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SwitchStates {
     pub active: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Window {
+    pub states: WindowStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowStates {
+    /// Position from `0` (closed) to `1` (open).
+    pub position: LoxoneUUID,
+    /// Movement, see [`Movement::from_value`].
+    pub direction: LoxoneUUID,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_position: Option<LoxoneUUID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_reason: Option<LoxoneUUID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info_text: Option<LoxoneUUID>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WindowMonitor {
+    pub details: WindowMonitorDetails,
+    pub states: WindowMonitorStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WindowMonitorDetails {
+    pub windows: Vec<WindowMonitorWindow>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowMonitorWindow {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_place: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowMonitorStates {
+    /// Comma separated [`WindowState`] bitmasks, in the order of the monitored windows.
+    pub window_states: LoxoneUUID,
+    pub num_open: LoxoneUUID,
+    pub num_closed: LoxoneUUID,
+    pub num_tilted: LoxoneUUID,
+    pub num_offline: LoxoneUUID,
+    pub num_locked: LoxoneUUID,
+    pub num_unlocked: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// State of a window monitored by a `WindowMonitor`, `0` if the window is offline.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WindowState(pub u8);
+
+impl WindowState {
+    pub const CLOSED: u8 = 1;
+    pub const TILTED: u8 = 2;
+    pub const OPEN: u8 = 4;
+    pub const LOCKED: u8 = 8;
+    pub const UNLOCKED: u8 = 16;
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn contains(self, bits: u8) -> bool {
+        self.0 & bits == bits
+    }
+
+    pub fn is_offline(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_closed(self) -> bool {
+        self.contains(Self::CLOSED)
+    }
+
+    pub fn is_tilted(self) -> bool {
+        self.contains(Self::TILTED)
+    }
+
+    pub fn is_open(self) -> bool {
+        self.contains(Self::OPEN)
+    }
+
+    pub fn is_locked(self) -> bool {
+        self.contains(Self::LOCKED)
+    }
+
+    pub fn is_unlocked(self) -> bool {
+        self.contains(Self::UNLOCKED)
+    }
+}
+
+impl WindowMonitor {
+    /// Parses the text of the `windowStates` state, unparsable entries are reported offline.
+    pub fn window_states(text: &str) -> Vec<WindowState> {
+        text.split(',').map(|state| WindowState(state.trim().parse().unwrap_or_default())).collect()
+    }
 }
 
 impl  LightControllerV2 {
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn add_mood(mood_id: u8) -> LoxoneMutation { format!("addMood/{}", mood_id) }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn add_to_favorite_mood(mood_id: u8) -> LoxoneMutation { format!("addToFavoriteMood/{}", mood_id) }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn change_to(mood_id: u8) -> LoxoneMutation { format!("changeTo/{}", mood_id) }
-    pub fn learn(mood_id: u8, mood_name: &str) -> LoxoneMutation { format!("learn/{}/{}", mood_id, mood_name) }
+    #[deprecated(note = "use `LightControllerV2Command`")]
+    pub fn learn(mood_id: u8, mood_name: &str) -> LoxoneMutation { MutationBuilder::new("learn").arg(mood_id).arg(mood_name).build() }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn minus() -> LoxoneMutation { String::from("minus") }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn move_favorite_mood(mood_id: u8, index: u8) -> LoxoneMutation { format!("moveFavoriteMood/{}/{}", mood_id, index) }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn move_additional_mood(mood_id: u8, index: u8) -> LoxoneMutation { format!("moveAdditionalMood/{}/{}", mood_id, index) }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn move_mood(mood_id: u8, index: u8) -> LoxoneMutation { format!("moveMood/{}/{}", mood_id, index) }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn plus() -> LoxoneMutation { String::from("plus") }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn remove(mood_id: u8) -> LoxoneMutation { format!("delete/{}", mood_id) }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn remove_from_favorite_mood(mood_id: u8) -> LoxoneMutation { format!("removeFromFavoriteMood/{}", mood_id) }
+    #[deprecated(note = "use `LightControllerV2Command`")]
     pub fn remove_mood(mood_id: u8) -> LoxoneMutation { format!("removeMood/{}", mood_id) }
-}
\ No newline at end of file
+}
+
+/// State UUIDs of a controller by state name, read without serializing the states.
+pub(crate) trait StateUuids {
+    fn state_uuids(&self) -> BTreeMap<String, LoxoneUUID>;
+}
+
+impl StateUuids for serde_json::Map<String, serde_json::Value> {
+    fn state_uuids(&self) -> BTreeMap<String, LoxoneUUID> {
+        self.iter().filter_map(|(state, uuid)| uuid.as_str().map(|uuid| (state.to_owned(), uuid.to_owned()))).collect()
+    }
+}
+
+/// Implements [`StateUuids`] for states structs, listing the required fields followed by the optional ones.
+macro_rules! state_uuids {
+    ($($states:ty { $($field:ident),* $(; $($optional:ident),*)? },)*) => {
+        $(impl StateUuids for $states {
+            fn state_uuids(&self) -> BTreeMap<String, LoxoneUUID> {
+                let mut uuids = self.extra.state_uuids();
+                $(uuids.insert(state_name(stringify!($field)), self.$field.to_owned());)*
+                $($(if let Some(uuid) = &self.$optional {
+                    uuids.insert(state_name(stringify!($optional)), uuid.to_owned());
+                })*)?
+                uuids
+            }
+        })*
+    };
+}
+
+state_uuids! {
+    AlarmStates { armed, next_level, next_level_delay, next_level_delay_total, level, start_time, armed_delay, armed_delay_total, sensors, disabled_move },
+    AudioZoneStates { server_state, play_state, client_state, power, volume, max_volume, volume_step, shuffle, repeat, song_name, artist, album, cover, duration, progress },
+    ClimateControllerStates { controls, current_mode, auto_mode, current_automatic, temperature_boundary_info, heating_temp_boundary, cooling_temp_boundary, actual_outdoor_temp, average_outdoor_temp, overwrite_reason, info_text, service_mode, next_maintenance, ventilation },
+    ColorPickerStates { color, favorites },
+    ColorPickerV2States { color, sequence, sequence_color_idx },
+    DaytimerStates { entries_and_default_value, mode, value; r#override, needs_activation },
+    DimmerStates { position, min, max, step },
+    GateStates { position, active, prevent_open, prevent_close },
+    InfoOnlyStates { value },
+    IRCV2DaytimerStates { entries_and_default_value, mode, mode_list, value },
+    IRoomControllerV2States { active_mode, operating_mode, override_entries, prepare_state, override_reason, temp_actual, temp_target, comfort_temperature, comfort_tolerance, absent_min_offset, absent_max_offset, frost_protect_temperature, heat_protect_temperature, comfort_temperature_offset, open_window },
+    JalousieStates { up, down, position, shade_position, safety_active, auto_allowed, auto_active, locked, info_text },
+    NfcCodeTouchStates { history_date, code_date, device_state, nfc_learn_result },
+    LightControllerV2States { active_moods, mood_list, favorite_moods, additional_moods },
+    SliderStates { value, error },
+    SmokeWaterAlarmStates { next_level, next_level_delay, next_level_delay_total, level, sensors, acoustic_alarm, test_alarm, alarm_cause, start_time, time_service_mode, are_alarm_signals_off },
+    SwitchStates { active },
+    WindowStates { position, direction; target_position, locked_reason, info_text },
+    WindowMonitorStates { window_states, num_open, num_closed, num_tilted, num_offline, num_locked, num_unlocked },
+}
+
+/// Returns the state name of the given field, as renamed by `#[serde(rename_all = "camelCase")]`.
+fn state_name(field: &str) -> String {
+    let mut words = field.trim_start_matches("r#").split('_');
+    let mut name = words.next().unwrap_or_default().to_owned();
+    for word in words {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
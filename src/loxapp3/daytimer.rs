@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::loxapp3::{LoxoneApp3, LoxoneController, LoxoneDaytimerEntry};
+
+/// Time of day of a daytimer entry, `24:00` marks the end of the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DaytimerTime {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl DaytimerTime {
+    pub const MIDNIGHT: Self = Self { hour: 0, minute: 0 };
+    pub const END_OF_DAY: Self = Self { hour: 24, minute: 0 };
+
+    /// Returns the time of day for the given minutes since midnight, `None` if out of range.
+    pub fn from_minutes(minutes: i32) -> Option<Self> {
+        match minutes {
+            0..=1440 => Some(Self { hour: (minutes / 60) as u8, minute: (minutes % 60) as u8 }),
+            _ => None,
+        }
+    }
+
+    /// Returns the minutes since midnight.
+    pub fn minutes(&self) -> i32 {
+        i32::from(self.hour) * 60 + i32::from(self.minute)
+    }
+}
+
+impl fmt::Display for DaytimerTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+/// Value of a daytimer entry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DaytimerValue {
+    Analog(f64),
+    Digital(bool),
+}
+
+impl DaytimerValue {
+    /// Interprets the given raw value according to the daytimer type.
+    pub fn new(value: f64, analog: bool) -> Self {
+        if analog {
+            Self::Analog(value)
+        } else {
+            Self::Digital(value != 0.0)
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Self::Analog(value) => *value,
+            Self::Digital(value) => f64::from(u8::from(*value)),
+        }
+    }
+}
+
+/// Entry of a daytimer schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaytimerEntry {
+    /// Operating mode ID the entry applies to.
+    pub mode: i32,
+    /// Operating mode name, as found in the structure file.
+    pub mode_name: Option<String>,
+    pub from: DaytimerTime,
+    pub to: DaytimerTime,
+    /// Whether the entry only becomes active once the daytimer is pulsed.
+    pub need_activate: bool,
+    pub value: DaytimerValue,
+}
+
+impl DaytimerEntry {
+    /// Converts the given raw entry, resolving its operating mode through the given `operating_modes`.
+    ///
+    /// Returns `None` if the entry times are out of range.
+    pub fn new(entry: &LoxoneDaytimerEntry, analog: bool, operating_modes: &HashMap<i8, String>) -> Option<Self> {
+        Some(Self {
+            mode: entry.mode,
+            mode_name: i8::try_from(entry.mode).ok().and_then(|mode| operating_modes.get(&mode)).cloned(),
+            from: DaytimerTime::from_minutes(entry.from)?,
+            to: DaytimerTime::from_minutes(entry.to)?,
+            need_activate: entry.need_activate != 0,
+            value: DaytimerValue::new(entry.value, analog),
+        })
+    }
+
+    /// Returns the raw entry as sent by the Miniserver.
+    pub fn to_raw(&self) -> LoxoneDaytimerEntry {
+        LoxoneDaytimerEntry {
+            mode: self.mode,
+            from: self.from.minutes(),
+            to: self.to.minutes(),
+            need_activate: i32::from(self.need_activate),
+            value: self.value.as_f64(),
+        }
+    }
+}
+
+impl LoxoneApp3 {
+    /// Converts the raw entries of a daytimer state into a schedule, dropping malformed entries.
+    pub fn daytimer_schedule(&self, entries: &[LoxoneDaytimerEntry], analog: bool) -> Vec<DaytimerEntry> {
+        entries.iter().filter_map(|entry| DaytimerEntry::new(entry, analog, &self.operating_modes)).collect()
+    }
+}
+
+impl LoxoneController {
+    /// Returns whether the daytimer holds analog values, `None` for controllers other than daytimers.
+    pub fn daytimer_analog(&self) -> Option<bool> {
+        match self {
+            Self::Daytimer(daytimer) => Some(daytimer.details.analog),
+            Self::IRCV2Daytimer(daytimer) => Some(daytimer.details.analog),
+            _ => None,
+        }
+    }
+}
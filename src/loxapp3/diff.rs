@@ -0,0 +1,229 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::loxapp3::{LoxoneApp3, LoxoneController, LoxoneUUID};
+
+/// Differences between two versions of the structure file.
+///
+/// The diff serializes to a JSON report and displays as a human-readable report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LoxoneApp3Diff {
+    pub added_controls: Vec<LoxoneDiffItem>,
+    pub removed_controls: Vec<LoxoneDiffItem>,
+    pub renamed_controls: Vec<LoxoneRename>,
+    pub moved_controls: Vec<LoxoneMove>,
+    pub changed_states: Vec<LoxoneStateChange>,
+    pub changed_types: Vec<LoxoneTypeChange>,
+    pub added_rooms: Vec<LoxoneDiffItem>,
+    pub removed_rooms: Vec<LoxoneDiffItem>,
+    pub added_categories: Vec<LoxoneDiffItem>,
+    pub removed_categories: Vec<LoxoneDiffItem>,
+}
+
+/// Control, room or category that has been added or removed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoxoneDiffItem {
+    pub uuid: LoxoneUUID,
+    pub name: String,
+}
+
+/// Control that has been renamed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoxoneRename {
+    pub uuid: LoxoneUUID,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+/// Control that has been moved to another room or category.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoxoneMove {
+    pub uuid: LoxoneUUID,
+    pub name: String,
+    pub old_room: Option<LoxoneUUID>,
+    pub new_room: Option<LoxoneUUID>,
+    pub old_cat: Option<LoxoneUUID>,
+    pub new_cat: Option<LoxoneUUID>,
+}
+
+/// State of a control whose UUID has changed, been added or been removed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoxoneStateChange {
+    pub uuid: LoxoneUUID,
+    pub name: String,
+    pub state: String,
+    pub old_uuid: Option<LoxoneUUID>,
+    pub new_uuid: Option<LoxoneUUID>,
+}
+
+/// Control whose controller type has changed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoxoneTypeChange {
+    pub uuid: LoxoneUUID,
+    pub name: String,
+    pub old_type: String,
+    pub new_type: String,
+}
+
+struct ControlInfo<'a> {
+    name: &'a str,
+    room: Option<&'a LoxoneUUID>,
+    cat: Option<&'a LoxoneUUID>,
+    controller: &'a LoxoneController,
+}
+
+impl LoxoneApp3Diff {
+    /// Compares the `old` and `new` structure files, including sub-controls.
+    pub fn new(old: &LoxoneApp3, new: &LoxoneApp3) -> Self {
+        let old_controls = controls(old);
+        let new_controls = controls(new);
+        let mut diff = Self::default();
+
+        for (uuid, new_control) in &new_controls {
+            let old_control = match old_controls.get(uuid) {
+                Some(old_control) => old_control,
+                None => {
+                    diff.added_controls.push(LoxoneDiffItem::new(uuid, new_control.name));
+                    continue
+                },
+            };
+            if old_control.name != new_control.name {
+                diff.renamed_controls.push(LoxoneRename { uuid: (*uuid).to_owned(), old_name: old_control.name.to_owned(), new_name: new_control.name.to_owned() });
+            }
+            if old_control.room != new_control.room || old_control.cat != new_control.cat {
+                diff.moved_controls.push(LoxoneMove {
+                    uuid: (*uuid).to_owned(),
+                    name: new_control.name.to_owned(),
+                    old_room: old_control.room.cloned(),
+                    new_room: new_control.room.cloned(),
+                    old_cat: old_control.cat.cloned(),
+                    new_cat: new_control.cat.cloned(),
+                });
+            }
+            if old_control.controller.type_name() != new_control.controller.type_name() {
+                diff.changed_types.push(LoxoneTypeChange {
+                    uuid: (*uuid).to_owned(),
+                    name: new_control.name.to_owned(),
+                    old_type: old_control.controller.type_name().to_owned(),
+                    new_type: new_control.controller.type_name().to_owned(),
+                });
+            }
+            let old_states = old_control.controller.states();
+            let new_states = new_control.controller.states();
+            for state in old_states.keys().chain(new_states.keys().filter(|state| !old_states.contains_key(*state))) {
+                let (old_uuid, new_uuid) = (old_states.get(state), new_states.get(state));
+                if old_uuid != new_uuid {
+                    diff.changed_states.push(LoxoneStateChange {
+                        uuid: (*uuid).to_owned(),
+                        name: new_control.name.to_owned(),
+                        state: state.to_owned(),
+                        old_uuid: old_uuid.cloned(),
+                        new_uuid: new_uuid.cloned(),
+                    });
+                }
+            }
+        }
+        diff.removed_controls = old_controls.iter().filter(|(uuid, _)| !new_controls.contains_key(*uuid)).map(|(uuid, control)| LoxoneDiffItem::new(uuid, control.name)).collect();
+
+        let old_rooms: BTreeMap<_, _> = old.rooms.iter().map(|(uuid, room)| (uuid, room.name.as_str())).collect();
+        let new_rooms: BTreeMap<_, _> = new.rooms.iter().map(|(uuid, room)| (uuid, room.name.as_str())).collect();
+        diff.added_rooms = added(&old_rooms, &new_rooms);
+        diff.removed_rooms = added(&new_rooms, &old_rooms);
+
+        let old_cats: BTreeMap<_, _> = old.cats.iter().map(|(uuid, cat)| (uuid, cat.name.as_str())).collect();
+        let new_cats: BTreeMap<_, _> = new.cats.iter().map(|(uuid, cat)| (uuid, cat.name.as_str())).collect();
+        diff.added_categories = added(&old_cats, &new_cats);
+        diff.removed_categories = added(&new_cats, &old_cats);
+
+        diff
+    }
+
+    /// Returns `true` if both structure files contain the same controls, rooms and categories.
+    pub fn is_empty(&self) -> bool {
+        self.added_controls.is_empty()
+            && self.removed_controls.is_empty()
+            && self.renamed_controls.is_empty()
+            && self.moved_controls.is_empty()
+            && self.changed_states.is_empty()
+            && self.changed_types.is_empty()
+            && self.added_rooms.is_empty()
+            && self.removed_rooms.is_empty()
+            && self.added_categories.is_empty()
+            && self.removed_categories.is_empty()
+    }
+}
+
+impl LoxoneDiffItem {
+    fn new(uuid: &str, name: &str) -> Self {
+        Self { uuid: uuid.to_owned(), name: name.to_owned() }
+    }
+}
+
+impl fmt::Display for LoxoneApp3Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no changes")
+        }
+        for room in &self.added_rooms {
+            writeln!(f, "+ room {:?} ({})", room.name, room.uuid)?;
+        }
+        for room in &self.removed_rooms {
+            writeln!(f, "- room {:?} ({})", room.name, room.uuid)?;
+        }
+        for cat in &self.added_categories {
+            writeln!(f, "+ category {:?} ({})", cat.name, cat.uuid)?;
+        }
+        for cat in &self.removed_categories {
+            writeln!(f, "- category {:?} ({})", cat.name, cat.uuid)?;
+        }
+        for control in &self.added_controls {
+            writeln!(f, "+ control {:?} ({})", control.name, control.uuid)?;
+        }
+        for control in &self.removed_controls {
+            writeln!(f, "- control {:?} ({})", control.name, control.uuid)?;
+        }
+        for rename in &self.renamed_controls {
+            writeln!(f, "~ control {:?} renamed to {:?} ({})", rename.old_name, rename.new_name, rename.uuid)?;
+        }
+        for change in &self.changed_types {
+            writeln!(f, "~ control {:?} changed type from {} to {} ({})", change.name, change.old_type, change.new_type, change.uuid)?;
+        }
+        for change in &self.moved_controls {
+            if change.old_room != change.new_room {
+                writeln!(f, "~ control {:?} moved from room {} to {} ({})", change.name, or_none(&change.old_room), or_none(&change.new_room), change.uuid)?;
+            }
+            if change.old_cat != change.new_cat {
+                writeln!(f, "~ control {:?} moved from category {} to {} ({})", change.name, or_none(&change.old_cat), or_none(&change.new_cat), change.uuid)?;
+            }
+        }
+        for change in &self.changed_states {
+            writeln!(f, "~ control {:?} state {} changed from {} to {} ({})", change.name, change.state, or_none(&change.old_uuid), or_none(&change.new_uuid), change.uuid)?;
+        }
+        Ok(())
+    }
+}
+
+fn or_none(uuid: &Option<LoxoneUUID>) -> &str {
+    uuid.as_deref().unwrap_or("none")
+}
+
+fn added(old: &BTreeMap<&LoxoneUUID, &str>, new: &BTreeMap<&LoxoneUUID, &str>) -> Vec<LoxoneDiffItem> {
+    new.iter().filter(|(uuid, _)| !old.contains_key(*uuid)).map(|(uuid, name)| LoxoneDiffItem::new(uuid, name)).collect()
+}
+
+fn controls(loxapp3: &LoxoneApp3) -> BTreeMap<&LoxoneUUID, ControlInfo<'_>> {
+    let mut controls = BTreeMap::new();
+    for control in loxapp3.controls.values() {
+        controls.insert(&control.uuid_action, ControlInfo { name: &control.name, room: control.room(), cat: control.cat(), controller: &control.controller });
+        insert_sub_controls(&mut controls, &control.controller);
+    }
+    controls
+}
+
+fn insert_sub_controls<'a>(controls: &mut BTreeMap<&'a LoxoneUUID, ControlInfo<'a>>, controller: &'a LoxoneController) {
+    for sub_control in controller.sub_controls().into_iter().flat_map(|sub_controls| sub_controls.values()) {
+        controls.insert(&sub_control.uuid_action, ControlInfo { name: &sub_control.name, room: None, cat: None, controller: &sub_control.controller });
+        insert_sub_controls(controls, &sub_control.controller);
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt::Write;
+
+use crate::time;
+
+/// Formats the given value according to a Loxone format string.
+///
+/// Besides C-style conversions such as `%.1f°C`, `%d%%` or `%s`, the following placeholders are supported:
+///
+/// * `<v>` and `<v.N>`: the value with `N` decimals.
+/// * `<v.u>`: the value in seconds as a duration, e.g. `1d 2h 5m`.
+/// * `<v.t>`: the value as Loxone timestamp, e.g. `2020-06-01 12:30`.
+/// * `<v.x>`: the value in hexadecimal.
+pub fn format_value(format: &str, value: f64) -> String {
+    let mut out = String::with_capacity(format.len());
+    let mut rest = format;
+    while let Some(pos) = rest.find(['%', '<']) {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let consumed = if rest.starts_with('%') {
+            format_conversion(&mut out, rest, value)
+        } else {
+            format_placeholder(&mut out, rest, value)
+        };
+        // Unsupported sequences are kept as is.
+        let consumed = consumed.unwrap_or_else(|| {
+            out.push_str(&rest[..1]);
+            1
+        });
+        rest = &rest[consumed..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Formats the C-style conversion at the start of `spec`, returning its length.
+fn format_conversion(out: &mut String, spec: &str, value: f64) -> Option<usize> {
+    let bytes = spec.as_bytes();
+    let mut pos = 1;
+    let (mut left, mut plus, mut space, mut zero) = (false, false, false, false);
+    while let Some(flag) = bytes.get(pos) {
+        match flag {
+            b'-' => left = true,
+            b'+' => plus = true,
+            b' ' => space = true,
+            b'0' => zero = true,
+            b'#' => {},
+            _ => break,
+        }
+        pos += 1;
+    }
+    let width = parse_number(bytes, &mut pos).unwrap_or(0);
+    let precision = if bytes.get(pos) == Some(&b'.') {
+        pos += 1;
+        Some(parse_number(bytes, &mut pos).unwrap_or(0))
+    } else {
+        None
+    };
+    // Length modifiers have no meaning for a `f64` value.
+    while let Some(b'l' | b'h' | b'L' | b'z') = bytes.get(pos) {
+        pos += 1;
+    }
+    let body = match bytes.get(pos)? {
+        b'%' => {
+            out.push('%');
+            return Some(pos + 1)
+        },
+        b'd' | b'i' | b'u' => format!("{:.0}", value.round().abs()),
+        b'f' | b'F' => format!("{:.*}", precision.unwrap_or(6), value.abs()),
+        b's' => format!("{:.*}", precision.unwrap_or_else(|| decimals(value)), value.abs()),
+        b'e' => format!("{:.*e}", precision.unwrap_or(6), value.abs()),
+        b'g' | b'G' => format!("{}", value.abs()),
+        b'x' => format!("{:x}", value.round().abs() as u64),
+        b'X' => format!("{:X}", value.round().abs() as u64),
+        _ => return None,
+    };
+    let sign = if value.is_sign_negative() && body.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        "-"
+    } else if plus {
+        "+"
+    } else if space {
+        " "
+    } else {
+        ""
+    };
+    let len = sign.len() + body.chars().count();
+    let padding = width.saturating_sub(len);
+    if left {
+        let _ = write!(out, "{}{}{:padding$}", sign, body, "", padding = padding);
+    } else if zero {
+        let _ = write!(out, "{}{}{}", sign, "0".repeat(padding), body);
+    } else {
+        let _ = write!(out, "{:padding$}{}{}", "", sign, body, padding = padding);
+    }
+    Some(pos + 1)
+}
+
+/// Formats the `<v…>` placeholder at the start of `spec`, returning its length.
+fn format_placeholder(out: &mut String, spec: &str, value: f64) -> Option<usize> {
+    let end = spec.find('>')?;
+    match &spec[1..end] {
+        "v" => out.push_str(&format!("{:.*}", decimals(value), value)),
+        "v.u" => out.push_str(&format_duration(value)),
+        "v.t" => out.push_str(&time::to_naive(value.round() as i64)?.format("%Y-%m-%d %H:%M").to_string()),
+        "v.x" => {
+            let _ = write!(out, "{:X}", value.round() as i64);
+        },
+        placeholder => {
+            let precision = placeholder.strip_prefix("v.")?.parse::<usize>().ok()?;
+            let _ = write!(out, "{:.*}", precision, value);
+        },
+    }
+    Some(end + 1)
+}
+
+/// Formats the given seconds as days, hours, minutes and seconds, omitting empty units.
+fn format_duration(seconds: f64) -> String {
+    let total = seconds.round().abs() as u64;
+    let units = [(total / 86400, "d"), (total / 3600 % 24, "h"), (total / 60 % 60, "m"), (total % 60, "s")];
+    let parts: Vec<_> = units.iter().filter(|(amount, _)| *amount > 0).map(|(amount, unit)| format!("{}{}", amount, unit)).collect();
+    match (parts.is_empty(), seconds < 0.0) {
+        (true, _) => "0s".to_owned(),
+        (false, true) => format!("-{}", parts.join(" ")),
+        (false, false) => parts.join(" "),
+    }
+}
+
+/// Returns the number of decimals needed to show the value, at most 3.
+fn decimals(value: f64) -> usize {
+    (0..3).find(|decimals| {
+        let scale = 10f64.powi(*decimals as i32);
+        ((value * scale).round() - value * scale).abs() < 1e-9
+    }).unwrap_or(3)
+}
+
+fn parse_number(bytes: &[u8], pos: &mut usize) -> Option<usize> {
+    let start = *pos;
+    while matches!(bytes.get(*pos), Some(b) if b.is_ascii_digit()) {
+        *pos += 1;
+    }
+    std::str::from_utf8(&bytes[start..*pos]).ok()?.parse().ok()
+}
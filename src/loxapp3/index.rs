@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use crate::loxapp3::{LoxoneApp3, LoxoneUUID};
+
+/// Index resolving state UUIDs to the control they belong to.
+#[derive(Debug, Clone, Default)]
+pub struct LoxoneStateIndex {
+    states: HashMap<LoxoneUUID, LoxoneStateRef>,
+}
+
+/// Control and state name a state UUID belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoxoneStateRef {
+    pub control: LoxoneUUID,
+    pub state: String,
+}
+
+impl LoxoneStateIndex {
+    /// Builds the index over all controls and sub-controls of the given structure file.
+    pub fn new(loxapp3: &LoxoneApp3) -> Self {
+        let mut index = Self::default();
+        for node in loxapp3.walk() {
+            for (state, uuid) in node.controller.states() {
+                index.states.insert(uuid, LoxoneStateRef { control: node.uuid.to_owned(), state });
+            }
+        }
+        index
+    }
+
+    pub fn get(&self, uuid: &str) -> Option<&LoxoneStateRef> {
+        self.states.get(uuid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
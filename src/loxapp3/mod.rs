@@ -1,18 +1,46 @@
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::de::{value::MapDeserializer, IntoDeserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
 
+pub mod calendar;
+pub mod color;
+pub mod commands;
 pub mod controllers;
+pub mod daytimer;
+pub mod diff;
+pub mod format;
+pub mod index;
+pub mod notifications;
+pub mod query;
+pub mod tasks;
+pub mod tree;
+pub mod weather;
 
 use controllers::*;
 
 /// Universally Unique Identifier (UUID).
 pub type LoxoneUUID = String;
 
+/// Returns `true` if the given string is a well-formed Loxone UUID.
+///
+/// Loxone UUIDs are made of four hex groups of 8, 4, 4 and 16 digits, sub-control UUIDs may be followed by a `/`-separated alphanumeric suffix.
+pub fn is_valid_uuid(uuid: &str) -> bool {
+    let (uuid, suffix) = match uuid.find('/') {
+        Some(pos) => (&uuid[..pos], Some(&uuid[pos + 1..])),
+        None => (uuid, None),
+    };
+    let groups: Vec<_> = uuid.split('-').collect();
+    let valid_groups = groups.len() == 4
+        && groups.iter().zip(&[8, 4, 4, 16]).all(|(group, len)| group.len() == *len && group.bytes().all(|b| b.is_ascii_hexdigit()));
+    let valid_suffix = suffix.map_or(true, |suffix| !suffix.is_empty() && suffix.bytes().all(|b| b.is_ascii_alphanumeric()));
+    valid_groups && valid_suffix
+}
+
 /// Command description.
 pub type LoxoneMutation = String;
 
 /// State that may change over time. 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum LoxoneState {
     Value(f64),
     Text(String, LoxoneUUID),
@@ -21,10 +49,11 @@ pub enum LoxoneState {
 }
 
 /// Miniserver global configuration aka. “structure file”.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoxoneApp3 {
- // TODO autopilot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autopilot: Option<LoxoneAutopilot>,
     pub cats: HashMap<LoxoneUUID, LoxoneCategory>,
     pub controls: HashMap<LoxoneUUID, LoxoneControl>,
     pub global_states: LoxoneGlobalStates,
@@ -34,11 +63,26 @@ pub struct LoxoneApp3 {
     pub operating_modes: HashMap<i8, String>,
     pub rooms: HashMap<LoxoneUUID, LoxoneRoom>,
     pub times: HashMap<String, LoxoneTime>,
- // TODO weather_server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weather_server: Option<LoxoneWeatherServer>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl LoxoneApp3 {
+    /// Returns the warnings for all controls and sub-controls that could not be fully parsed.
+    pub fn warnings(&self) -> Vec<LoxoneWarning> {
+        self.walk().filter_map(|node| node.controller.warning().map(|message| LoxoneWarning {
+            uuid: node.uuid.to_owned(),
+            name: node.name.to_owned(),
+            type_name: node.controller.type_name().to_owned(),
+            message,
+        })).collect()
+    }
 }
 
 /// Category that is used to group controls logically.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoxoneCategory {
     pub color: String,
@@ -47,46 +91,259 @@ pub struct LoxoneCategory {
     pub name: String,
     pub r#type: String,
     pub uuid: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Control that is used to represent sensors and actuators.
-#[derive(Debug, Deserialize)]
+///
+/// Optional fields keep whether they were present in the structure file, so that it is serialized as received.
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoxoneControl {
-    pub cat: Option<LoxoneUUID>,
+    /// `None` if absent, `Some(None)` if `null`, see [`LoxoneControl::cat`].
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub cat: Option<Option<LoxoneUUID>>,
     #[serde(flatten)]
     pub controller: LoxoneController,
-    pub default_icon: Option<String>,
+    /// `None` if absent, `Some(None)` if `null`, see [`LoxoneControl::default_icon`].
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub default_icon: Option<Option<String>>,
     pub default_rating: u8,
- // TODO has_control_notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_control_notes: Option<bool>,
     pub is_favorite: bool,
     pub is_secured: bool,
     pub name: String,
- // TODO restriction
-    pub room: Option<LoxoneUUID>,
- // TODO secured_details
- // TODO statistics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<LoxoneRestrictions>,
+    /// `None` if absent, `Some(None)` if `null`, see [`LoxoneControl::room`].
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub room: Option<Option<LoxoneUUID>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secured_details: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistic: Option<LoxoneStatistic>,
     pub uuid_action: LoxoneUUID,
 }
 
-#[derive(Debug, Deserialize)]
+impl LoxoneControl {
+    pub fn cat(&self) -> Option<&LoxoneUUID> {
+        self.cat.as_ref().and_then(Option::as_ref)
+    }
+
+    pub fn default_icon(&self) -> Option<&str> {
+        self.default_icon.as_ref().and_then(Option::as_deref)
+    }
+
+    pub fn has_control_notes(&self) -> bool {
+        self.has_control_notes.unwrap_or_default()
+    }
+
+    pub fn restrictions(&self) -> LoxoneRestrictions {
+        self.restrictions.unwrap_or_default()
+    }
+
+    pub fn room(&self) -> Option<&LoxoneUUID> {
+        self.room.as_ref().and_then(Option::as_ref)
+    }
+
+    pub fn secured_details(&self) -> bool {
+        self.secured_details.unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoxoneSubControl {
     #[serde(flatten)]
     pub controller: LoxoneController,
     pub default_rating: u8,
- // TODO has_control_notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_control_notes: Option<bool>,
     pub is_favorite: bool,
     pub is_secured: bool,
     pub name: String,
- // TODO restriction
- // TODO secured_details
- // TODO statistics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<LoxoneRestrictions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secured_details: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistic: Option<LoxoneStatistic>,
+    pub uuid_action: LoxoneUUID,
+}
+
+impl LoxoneSubControl {
+    pub fn has_control_notes(&self) -> bool {
+        self.has_control_notes.unwrap_or_default()
+    }
+
+    pub fn restrictions(&self) -> LoxoneRestrictions {
+        self.restrictions.unwrap_or_default()
+    }
+
+    pub fn secured_details(&self) -> bool {
+        self.secured_details.unwrap_or_default()
+    }
+}
+
+/// Restrictions that apply when operating a control.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct LoxoneRestrictions(pub u32);
+
+impl LoxoneRestrictions {
+    /// Control can only be operated from within the local network.
+    pub const INTERNAL_ONLY: u32 = 1 << 0;
+    /// Control can be viewed but not operated.
+    pub const READ_ONLY: u32 = 1 << 1;
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, flag: u32) -> bool {
+        self.0 & flag == flag
+    }
+
+    pub fn is_internal_only(&self) -> bool {
+        self.contains(Self::INTERNAL_ONLY)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.contains(Self::READ_ONLY)
+    }
+}
+
+/// Statistic definition of a control.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LoxoneStatistic {
+    pub frequency: LoxoneStatisticFrequency,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<LoxoneStatisticOutput>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Interval in which statistic values are recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum LoxoneStatisticFrequency {
+    None,
+    EveryChange,
+    Minute,
+    FiveMinutes,
+    TenMinutes,
+    ThirtyMinutes,
+    Hour,
+    Unknown(u8),
+}
+
+impl From<u8> for LoxoneStatisticFrequency {
+    fn from(val: u8) -> Self {
+        match val {
+            0 => Self::None,
+            1 => Self::EveryChange,
+            2 => Self::Minute,
+            3 => Self::FiveMinutes,
+            4 => Self::TenMinutes,
+            5 => Self::ThirtyMinutes,
+            6 => Self::Hour,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<LoxoneStatisticFrequency> for u8 {
+    fn from(frequency: LoxoneStatisticFrequency) -> Self {
+        match frequency {
+            LoxoneStatisticFrequency::None => 0,
+            LoxoneStatisticFrequency::EveryChange => 1,
+            LoxoneStatisticFrequency::Minute => 2,
+            LoxoneStatisticFrequency::FiveMinutes => 3,
+            LoxoneStatisticFrequency::TenMinutes => 4,
+            LoxoneStatisticFrequency::ThirtyMinutes => 5,
+            LoxoneStatisticFrequency::Hour => 6,
+            LoxoneStatisticFrequency::Unknown(val) => val,
+        }
+    }
+}
+
+/// Output that is recorded by the statistic.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoxoneStatisticOutput {
+    pub id: u8,
+    pub name: String,
+    pub format: String,
+    pub uuid: LoxoneUUID,
+    pub visu_type: u8,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Automatic rules configured on the Miniserver.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoxoneAutopilot {
+    pub name: String,
     pub uuid_action: LoxoneUUID,
+    pub states: LoxoneAutopilotStates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LoxoneAutopilotStates {
+    pub changed: LoxoneUUID,
+    pub history: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Weather server configuration and field mapping.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoxoneWeatherServer {
+    pub states: LoxoneWeatherServerStates,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub format: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub weather_type_texts: HashMap<i32, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub weather_field_types: HashMap<u8, LoxoneWeatherFieldType>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LoxoneWeatherServerStates {
+    pub actual: LoxoneUUID,
+    pub forecast: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Description of a weather data field.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LoxoneWeatherFieldType {
+    pub id: u8,
+    pub name: String,
+    pub analog: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub unit: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub format: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Global states that affect the whole Miniserver.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoxoneGlobalStates {
     pub sunset: LoxoneUUID,
@@ -102,19 +359,23 @@ pub struct LoxoneGlobalStates {
     pub past_tasks: LoxoneUUID,
     pub modifications: LoxoneUUID,
     pub user_settings: LoxoneUUID,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// System status message.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoxoneMessage {
     pub name: String,
     pub uuid_action: LoxoneUUID,
     pub states: HashMap<String, LoxoneUUID>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Static informations on the Miniserver and it’s configuration.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoxoneMiniserverInfo {
     pub serial_nr: String,
@@ -136,9 +397,11 @@ pub struct LoxoneMiniserverInfo {
     pub current_user: LoxoneUser,
     pub device_monitor: LoxoneUUID,
     pub language_code: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoxoneUser {
     pub uuid: LoxoneUUID,
@@ -146,10 +409,12 @@ pub struct LoxoneUser {
     pub is_admin: bool,
     pub change_password: bool,
     pub user_rights: u16,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Room that is used to group controls based on their location.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoxoneRoom {
     pub uuid: LoxoneUUID,
@@ -158,38 +423,104 @@ pub struct LoxoneRoom {
     pub default_rating: u8,
     pub is_favorite: bool,
     pub r#type: u8,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct LoxoneTime {
     pub id: u16,
     pub name: String,
     pub analog: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(tag = "type")]
-pub enum LoxoneController {
+macro_rules! loxone_controllers {
+    (@type) => { serde_json::Value };
+    (@type $inner:ty) => { $inner };
+    (@parse $raw:ident) => { $raw.clone() };
+    (@parse $raw:ident $inner:ty) => { <$inner>::deserialize(untagged($raw))? };
+    (@states $controller:ident) => { state_uuids($controller.get("states")) };
+    (@states $controller:ident $inner:ty) => { $controller.states.state_uuids() };
+    (@serialize $name:ident $controller:ident $serializer:ident) => { $controller.serialize($serializer) };
+    (@serialize $name:ident $controller:ident $serializer:ident $inner:ty) => {
+        Tagged { r#type: stringify!($name), controller: $controller }.serialize($serializer)
+    };
+    ($($name:ident $(($inner:ty))?,)*) => {
+        /// Controller type along with its type specific details and states.
+        ///
+        /// Controller types without a dedicated model keep their raw JSON.
+        #[derive(Debug)]
+        pub enum LoxoneController {
+            $($name(loxone_controllers!(@type $($inner)?)),)*
+            /// Controller type that is unknown or that could not be parsed.
+            Unknown {
+                type_name: String,
+                raw: serde_json::Value,
+                /// Parse error of a known but malformed controller type.
+                error: Option<String>,
+            },
+        }
+
+        impl LoxoneController {
+            /// Returns the controller type as found in the structure file.
+            pub fn type_name(&self) -> &str {
+                match self {
+                    $(Self::$name(_) => stringify!($name),)*
+                    Self::Unknown { type_name, .. } => type_name,
+                }
+            }
+
+            /// Parses the given raw JSON, failing if a known controller type is malformed.
+            pub fn try_from_value(raw: &serde_json::Value) -> Result<Self, serde_json::Error> {
+                match raw.get("type").and_then(serde_json::Value::as_str).unwrap_or_default() {
+                    $(stringify!($name) => Ok(Self::$name(loxone_controllers!(@parse raw $($inner)?))),)*
+                    type_name => Ok(Self::Unknown { type_name: type_name.to_owned(), raw: raw.clone(), error: None }),
+                }
+            }
+
+            /// Returns the state UUIDs of the controller by state name.
+            pub fn states(&self) -> BTreeMap<String, LoxoneUUID> {
+                match self {
+                    $(Self::$name(controller) => loxone_controllers!(@states controller $($inner)?),)*
+                    Self::Unknown { raw, .. } => state_uuids(raw.get("states")),
+                }
+            }
+        }
+
+        impl Serialize for LoxoneController {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    $(Self::$name(controller) => loxone_controllers!(@serialize $name controller serializer $($inner)?),)*
+                    Self::Unknown { raw, .. } => raw.serialize(serializer),
+                }
+            }
+        }
+    };
+}
+
+loxone_controllers! {
     AalEmergency,
     AalSmartAlarm,
-    Alarm,
+    Alarm(Alarm),
     AlarmChain,
     AlarmClock,
-    AudioZone,
+    AudioZone(AudioZone),
     CarCharger,
-    CentralAlarm,
-    CentralAudioZone,
-    CentralGate,
-    CentralJalousie,
+    CentralAlarm(CentralAlarm),
+    CentralAudioZone(CentralAudioZone),
+    CentralGate(CentralGate),
+    CentralJalousie(CentralJalousie),
     CentralLightController(CentralLightController),
     ClimateController(ClimateController),
     ColorPicker(ColorPicker),
     ColorPickerV2(ColorPickerV2),
-    Daytimer,
+    Daytimer(Daytimer),
     Dimmer(Dimmer),
     FanController,
     Fronius,
-    Gate,
+    Gate(Gate),
     Heatmixer,
     Hourcounter,
     InfoOnlyAnalog(InfoOnlyAnalog),
@@ -198,8 +529,8 @@ pub enum LoxoneController {
     IntelligentRoomControllerIntercom,
     IRCV2Daytimer(IRCV2Daytimer),
     IRoomController,
-    IRoomControllerV2(IRoomControllerV2), 
-    Jalousie,
+    IRoomControllerV2(IRoomControllerV2),
+    Jalousie(Jalousie),
     NfcCodeTouch(NfcCodeTouch),
     LightController,
     LightControllerV2(LightControllerV2),
@@ -226,12 +557,166 @@ pub enum LoxoneController {
     ValueSelector,
     Ventilation,
     Webpage,
-    Window,
-    WindowMonitor,
+    Window(Window),
+    WindowMonitor(WindowMonitor),
+}
+
+impl LoxoneController {
+    /// Parses the given raw JSON, falling back to [`LoxoneController::Unknown`] if malformed.
+    pub fn from_value(raw: serde_json::Value) -> Self {
+        match Self::try_from_value(&raw) {
+            Ok(controller) => controller,
+            Err(err) => Self::Unknown {
+                type_name: raw.get("type").and_then(serde_json::Value::as_str).unwrap_or_default().to_owned(),
+                raw,
+                error: Some(err.to_string()),
+            },
+        }
+    }
+
+    /// Returns the sub-controls managed by the controller.
+    pub fn sub_controls(&self) -> Option<&HashMap<LoxoneUUID, LoxoneSubControl>> {
+        match self {
+            Self::IRoomControllerV2(controller) => Some(&controller.sub_controls),
+            Self::LightControllerV2(controller) => Some(&controller.sub_controls),
+            Self::SmokeAlarm(controller) | Self::WaterAlarm(controller) => Some(&controller.sub_controls),
+            _ => None,
+        }
+    }
+
+    /// Returns the UUIDs of the controls managed by a central controller.
+    pub fn central_controls(&self) -> Option<Vec<&LoxoneUUID>> {
+        match self {
+            Self::CentralAlarm(controller) => Some(controller.details.controls.iter().map(|control| &control.uuid).collect()),
+            Self::CentralAudioZone(controller) => Some(controller.details.controls.iter().map(|control| &control.uuid).collect()),
+            Self::CentralGate(controller) => Some(controller.details.controls.iter().map(|control| &control.uuid).collect()),
+            Self::CentralJalousie(controller) => Some(controller.details.controls.iter().map(|control| &control.uuid).collect()),
+            Self::CentralLightController(controller) => Some(controller.details.controls.iter().map(|control| &control.uuid).collect()),
+            _ => None,
+        }
+    }
+
+    /// Returns the reason why the controller could not be modelled, if any.
+    pub fn warning(&self) -> Option<String> {
+        match self {
+            Self::Unknown { type_name, error: Some(err), .. } => Some(format!("malformed {} controller: {}", type_name, err)),
+            Self::Unknown { type_name, .. } if type_name.is_empty() => Some(String::from("missing controller type")),
+            Self::Unknown { type_name, .. } => Some(format!("unsupported controller type {}", type_name)),
+            _ => None,
+        }
+    }
+}
+
+/// Controller data that can be borrowed from a [`LoxoneController`].
+pub trait LoxoneControllerType {
+    /// Returns the controller data if the controller is of this type.
+    fn from_controller(controller: &LoxoneController) -> Option<&Self>;
+}
+
+impl LoxoneControllerType for LoxoneController {
+    fn from_controller(controller: &LoxoneController) -> Option<&Self> {
+        Some(controller)
+    }
+}
+
+macro_rules! loxone_controller_types {
+    ($($inner:ty => $($name:ident)|+,)*) => {
+        $(impl LoxoneControllerType for $inner {
+            fn from_controller(controller: &LoxoneController) -> Option<&Self> {
+                match controller {
+                    $(LoxoneController::$name(controller))|+ => Some(controller),
+                    _ => None,
+                }
+            }
+        })*
+    };
+}
+
+loxone_controller_types! {
+    Alarm => Alarm,
+    AudioZone => AudioZone,
+    CentralAlarm => CentralAlarm,
+    CentralAudioZone => CentralAudioZone,
+    CentralGate => CentralGate,
+    CentralJalousie => CentralJalousie,
+    CentralLightController => CentralLightController,
+    ClimateController => ClimateController,
+    ColorPicker => ColorPicker,
+    ColorPickerV2 => ColorPickerV2,
+    Daytimer => Daytimer,
+    Dimmer => Dimmer,
+    Gate => Gate,
+    InfoOnlyAnalog => InfoOnlyAnalog,
+    InfoOnlyDigital => InfoOnlyDigital,
+    IRCV2Daytimer => IRCV2Daytimer,
+    IRoomControllerV2 => IRoomControllerV2,
+    Jalousie => Jalousie,
+    NfcCodeTouch => NfcCodeTouch,
+    LightControllerV2 => LightControllerV2,
+    Slider => Slider,
+    SmokeWaterAlarm => SmokeAlarm | WaterAlarm,
+    Switch => Switch,
+    Window => Window,
+    WindowMonitor => WindowMonitor,
+}
+
+impl<'de> Deserialize<'de> for LoxoneController {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_json::Value::deserialize(deserializer).map(Self::from_value)
+    }
+}
+
+#[derive(Serialize)]
+struct Tagged<'a, T> {
+    r#type: &'a str,
+    #[serde(flatten)]
+    controller: &'a T,
+}
+
+struct ValueRef<'a>(&'a serde_json::Value);
+
+impl<'a> IntoDeserializer<'a, serde_json::Error> for ValueRef<'a> {
+    type Deserializer = &'a serde_json::Value;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self.0
+    }
+}
+
+/// Returns a deserializer over the raw controller JSON without its `type` tag.
+fn untagged(raw: &serde_json::Value) -> MapDeserializer<'_, impl Iterator<Item = (&str, ValueRef<'_>)>, serde_json::Error> {
+    let fields = raw.as_object().into_iter().flatten().filter(|(key, _)| key.as_str() != "type");
+    MapDeserializer::new(fields.map(|(key, val)| (key.as_str(), ValueRef(val))))
+}
+
+/// Collects the state UUIDs of the given `states` object, skipping values that are not UUIDs.
+fn state_uuids(states: Option<&serde_json::Value>) -> BTreeMap<String, LoxoneUUID> {
+    match states {
+        Some(serde_json::Value::Object(states)) => states.state_uuids(),
+        _ => BTreeMap::new(),
+    }
+}
+
+/// Deserializes a present field, including `null`, as `Some`, so that it can be told apart from a missing field.
+fn deserialize_some<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
+
+pub(crate) fn is_false(val: &bool) -> bool {
+    !val
+}
+
+/// Warning raised for a control that could not be fully parsed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoxoneWarning {
+    pub uuid: LoxoneUUID,
+    pub name: String,
+    pub type_name: String,
+    pub message: String,
 }
 
 /// Day timer event entry.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct LoxoneDaytimerEntry {
     pub mode: i32,
     pub from: i32,
@@ -241,7 +726,7 @@ pub struct LoxoneDaytimerEntry {
 }
 
 /// Weather event entry.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct LoxoneWeatherEntry {
     pub timestamp: i32,
     pub weather_type: i32,
@@ -253,5 +738,5 @@ pub struct LoxoneWeatherEntry {
     pub dew_point: f64,
     pub precipitation: f64,
     pub wind_speed: f64,
-    pub barometic_pressure: f64,
+    pub barometric_pressure: f64,
 }
\ No newline at end of file
@@ -0,0 +1,261 @@
+use chrono::{DateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::loxapp3::commands::MutationBuilder;
+use crate::loxapp3::{LoxoneApp3, LoxoneControl, LoxoneMessage, LoxoneMutation, LoxoneRoom, LoxoneUUID};
+use crate::time;
+
+/// Notification sent through the `notifications` global state.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Notification {
+    pub uid: String,
+    /// Seconds since 2009-01-01 Miniserver time.
+    #[serde(rename = "ts")]
+    pub timestamp: i64,
+    pub r#type: NotificationType,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<NotificationData>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NotificationData {
+    #[serde(rename = "lvl", skip_serializing_if = "Option::is_none")]
+    pub level: Option<NotificationLevel>,
+    /// Control that caused the notification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<LoxoneUUID>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum NotificationType {
+    Normal,
+    Unknown(u8),
+}
+
+impl From<u8> for NotificationType {
+    fn from(val: u8) -> Self {
+        match val {
+            10 => Self::Normal,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<NotificationType> for u8 {
+    fn from(notification_type: NotificationType) -> Self {
+        match notification_type {
+            NotificationType::Normal => 10,
+            NotificationType::Unknown(val) => val,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum NotificationLevel {
+    Info,
+    Error,
+    SystemError,
+    Unknown(u8),
+}
+
+impl From<u8> for NotificationLevel {
+    fn from(val: u8) -> Self {
+        match val {
+            1 => Self::Info,
+            2 => Self::Error,
+            3 => Self::SystemError,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<NotificationLevel> for u8 {
+    fn from(level: NotificationLevel) -> Self {
+        match level {
+            NotificationLevel::Info => 1,
+            NotificationLevel::Error => 2,
+            NotificationLevel::SystemError => 3,
+            NotificationLevel::Unknown(val) => val,
+        }
+    }
+}
+
+impl Notification {
+    /// Parses the text of the `notifications` global state, holding either a single notification or a list.
+    pub fn parse(text: &str) -> Result<Vec<Self>, serde_json::Error> {
+        let text = text.trim();
+        if text.is_empty() {
+            Ok(Vec::new())
+        } else if text.starts_with('[') {
+            serde_json::from_str(text)
+        } else {
+            Ok(vec![serde_json::from_str(text)?])
+        }
+    }
+
+    /// Returns the time of the notification in the time zone `tz` of the Miniserver.
+    pub fn time<Tz: TimeZone>(&self, tz: &Tz) -> Option<DateTime<Tz>> {
+        time::to_datetime(self.timestamp, tz)
+    }
+
+    pub fn level(&self) -> Option<NotificationLevel> {
+        self.data.as_ref().and_then(|data| data.level)
+    }
+}
+
+/// System status entries of a message center, as returned by its `getEntries/2` command.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MessageCenter {
+    #[serde(default)]
+    pub entries: Vec<MessageCenterEntry>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// System status message.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageCenterEntry {
+    pub entry_uuid: LoxoneUUID,
+    pub event_id: u32,
+    pub severity: MessageSeverity,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default, rename = "desc")]
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_uuid: Option<LoxoneUUID>,
+    /// Controls affected by the message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affected_uuids: Vec<LoxoneUUID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affected_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_uuid: Option<LoxoneUUID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation_place: Option<String>,
+    /// Occurrences of the message, in seconds since 2009-01-01 Miniserver time.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timestamps: Vec<i64>,
+    #[serde(default)]
+    pub is_historic: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmed_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help_link: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum MessageSeverity {
+    Info,
+    Warning,
+    Error,
+    Critical,
+    Unknown(u8),
+}
+
+impl From<u8> for MessageSeverity {
+    fn from(val: u8) -> Self {
+        match val {
+            1 => Self::Info,
+            2 => Self::Warning,
+            3 => Self::Error,
+            4 => Self::Critical,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<MessageSeverity> for u8 {
+    fn from(severity: MessageSeverity) -> Self {
+        match severity {
+            MessageSeverity::Info => 1,
+            MessageSeverity::Warning => 2,
+            MessageSeverity::Error => 3,
+            MessageSeverity::Critical => 4,
+            MessageSeverity::Unknown(val) => val,
+        }
+    }
+}
+
+impl MessageCenter {
+    /// Parses the reply of the `getEntries/2` command, holding either the message center or its list of entries.
+    pub fn parse(text: &str) -> Result<Self, serde_json::Error> {
+        let text = text.trim();
+        if text.starts_with('[') {
+            Ok(Self { entries: serde_json::from_str(text)?, extra: serde_json::Map::new() })
+        } else {
+            serde_json::from_str(text)
+        }
+    }
+
+    /// Returns the entries that are still relevant, dropping historic ones.
+    pub fn active(&self) -> impl Iterator<Item = &MessageCenterEntry> {
+        self.entries.iter().filter(|entry| !entry.is_historic)
+    }
+
+    /// Returns the active entries that have not been confirmed yet.
+    pub fn unconfirmed(&self) -> impl Iterator<Item = &MessageCenterEntry> {
+        self.active().filter(|entry| entry.confirmed_at.is_none())
+    }
+
+    pub fn get(&self, entry_uuid: &str) -> Option<&MessageCenterEntry> {
+        self.entries.iter().find(|entry| entry.entry_uuid == entry_uuid)
+    }
+}
+
+impl MessageCenterEntry {
+    /// Returns the latest occurrence of the message in the time zone `tz` of the Miniserver.
+    pub fn last_occurrence<Tz: TimeZone>(&self, tz: &Tz) -> Option<DateTime<Tz>> {
+        self.timestamps.iter().max().and_then(|timestamp| time::to_datetime(*timestamp, tz))
+    }
+
+    pub fn room<'a>(&self, loxapp3: &'a LoxoneApp3) -> Option<&'a LoxoneRoom> {
+        self.room_uuid.as_ref().and_then(|room| loxapp3.rooms.get(room))
+    }
+
+    /// Returns the affected controls found in the structure file.
+    pub fn affected_controls<'a>(&self, loxapp3: &'a LoxoneApp3) -> Vec<&'a LoxoneControl> {
+        self.affected_uuids.iter().filter_map(|uuid| loxapp3.controls.get(uuid)).collect()
+    }
+}
+
+impl LoxoneApp3 {
+    /// Returns the message center named `SystemStatus`, if configured.
+    pub fn system_status(&self) -> Option<&LoxoneMessage> {
+        self.message_center.values().find(|message| message.name == "SystemStatus")
+    }
+}
+
+/// Command sent to a message center.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageCenterCommand {
+    /// Acknowledges the given entry, marking it as read.
+    Acknowledge(LoxoneUUID),
+    /// Confirms the given entry, moving it to the history once the cause is resolved.
+    Confirm(LoxoneUUID),
+}
+
+impl MessageCenterCommand {
+    pub fn mutation(&self) -> LoxoneMutation {
+        match self {
+            Self::Acknowledge(entry_uuid) => MutationBuilder::new("markAsRead").arg(entry_uuid).build(),
+            Self::Confirm(entry_uuid) => MutationBuilder::new("confirm").arg(entry_uuid).build(),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+use std::vec;
+
+use crate::loxapp3::{LoxoneApp3, LoxoneCategory, LoxoneController, LoxoneControllerType, LoxoneRoom, LoxoneUUID};
+
+/// Minimum similarity for a name to match a fuzzy search pattern that it does not contain.
+const MIN_SIMILARITY: f64 = 0.6;
+
+/// Reference to a control or sub-control along with its room and category.
+///
+/// Sub-controls inherit the room and category of the control they belong to.
+#[derive(Debug)]
+pub struct LoxoneControlRef<'a, T = LoxoneController> {
+    pub uuid: &'a LoxoneUUID,
+    pub name: &'a str,
+    pub is_favorite: bool,
+    pub is_secured: bool,
+    pub room: Option<&'a LoxoneRoom>,
+    pub cat: Option<&'a LoxoneCategory>,
+    /// Control the sub-control belongs to, `None` for top-level controls.
+    pub parent: Option<&'a LoxoneUUID>,
+    pub controller: &'a LoxoneController,
+    /// Typed controller data.
+    pub data: &'a T,
+}
+
+impl<'a, T> Clone for LoxoneControlRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for LoxoneControlRef<'a, T> {}
+
+impl<'a, T> LoxoneControlRef<'a, T> {
+    fn cast<U: LoxoneControllerType>(self) -> Option<LoxoneControlRef<'a, U>> {
+        U::from_controller(self.controller).map(|data| LoxoneControlRef {
+            uuid: self.uuid,
+            name: self.name,
+            is_favorite: self.is_favorite,
+            is_secured: self.is_secured,
+            room: self.room,
+            cat: self.cat,
+            parent: self.parent,
+            controller: self.controller,
+            data,
+        })
+    }
+}
+
+/// Query over the controls and sub-controls of a structure file.
+///
+/// Controls are ordered by name unless a fuzzy [`search`](LoxoneQuery::search) ranked them.
+#[derive(Debug, Clone)]
+pub struct LoxoneQuery<'a, T = LoxoneController> {
+    controls: Vec<LoxoneControlRef<'a, T>>,
+}
+
+impl LoxoneApp3 {
+    /// Returns a query over all controls and sub-controls.
+    pub fn controls(&self) -> LoxoneQuery<'_> {
+        let mut controls: Vec<_> = self.walk().map(|node| LoxoneControlRef {
+            uuid: node.uuid,
+            name: node.name,
+            is_favorite: node.is_favorite,
+            is_secured: node.is_secured,
+            room: node.room.and_then(|room| self.rooms.get(room)),
+            cat: node.cat.and_then(|cat| self.cats.get(cat)),
+            parent: node.parent(),
+            controller: node.controller,
+            data: node.controller,
+        }).collect();
+        controls.sort_by(|a, b| a.name.cmp(b.name).then_with(|| a.uuid.cmp(b.uuid)));
+        LoxoneQuery { controls }
+    }
+}
+
+impl<'a, T> LoxoneQuery<'a, T> {
+    /// Keeps the controls located in the room with the given name or UUID.
+    pub fn in_room(self, room: &str) -> Self {
+        self.filter(|control| matches!(control.room, Some(r) if r.uuid == room || eq_ignore_case(&r.name, room)))
+    }
+
+    /// Keeps the controls assigned to the category with the given name or UUID.
+    pub fn in_category(self, cat: &str) -> Self {
+        self.filter(|control| matches!(control.cat, Some(c) if c.uuid == cat || eq_ignore_case(&c.name, cat)))
+    }
+
+    /// Keeps the controls of the given controller type.
+    pub fn of_type<U: LoxoneControllerType>(self) -> LoxoneQuery<'a, U> {
+        LoxoneQuery { controls: self.controls.into_iter().filter_map(LoxoneControlRef::cast).collect() }
+    }
+
+    /// Keeps the controls marked as favorite.
+    pub fn favorites(self) -> Self {
+        self.filter(|control| control.is_favorite)
+    }
+
+    /// Keeps the top-level controls, dropping sub-controls.
+    pub fn top_level(self) -> Self {
+        self.filter(|control| control.parent.is_none())
+    }
+
+    /// Keeps the controls with the given name, ignoring case.
+    pub fn named(self, name: &str) -> Self {
+        self.filter(|control| eq_ignore_case(control.name, name))
+    }
+
+    /// Keeps the controls whose name resembles the given pattern, best matches first.
+    pub fn search(self, pattern: &str) -> Self {
+        let pattern = pattern.to_lowercase();
+        let mut scored: Vec<_> = self.controls.into_iter().filter_map(|control| fuzzy_score(&pattern, control.name).map(|score| (score, control))).collect();
+        // The sort is stable, controls with equal scores keep their order.
+        scored.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        Self { controls: scored.into_iter().map(|(_, control)| control).collect() }
+    }
+
+    /// Keeps the controls matching the given predicate.
+    pub fn filter<P: FnMut(&LoxoneControlRef<'a, T>) -> bool>(mut self, predicate: P) -> Self {
+        self.controls.retain(predicate);
+        self
+    }
+
+    pub fn first(&self) -> Option<LoxoneControlRef<'a, T>> {
+        self.controls.first().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.controls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.controls.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = LoxoneControlRef<'a, T>> + '_ {
+        self.controls.iter().copied()
+    }
+}
+
+impl<'a, T> IntoIterator for LoxoneQuery<'a, T> {
+    type Item = LoxoneControlRef<'a, T>;
+    type IntoIter = vec::IntoIter<LoxoneControlRef<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.controls.into_iter()
+    }
+}
+
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+/// Scores how well `name` matches the lowercase `pattern`, from `0.0` to `1.0`.
+///
+/// Names containing the pattern always match, other names must be similar enough to it, either as a whole or by one of their words.
+fn fuzzy_score(pattern: &str, name: &str) -> Option<f64> {
+    let name = name.to_lowercase();
+    if name == pattern {
+        return Some(1.0)
+    }
+    if name.starts_with(pattern) {
+        return Some(0.9)
+    }
+    if name.contains(pattern) {
+        return Some(0.8)
+    }
+    let similarity = name.split_whitespace().chain(Some(name.as_str())).map(|word| similarity(pattern, word)).fold(0.0, f64::max);
+    if similarity >= MIN_SIMILARITY {
+        Some(similarity * 0.7)
+    } else {
+        None
+    }
+}
+
+/// Normalized Levenshtein similarity between `a` and `b`.
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let len = a.len().max(b.len());
+    if len == 0 {
+        return 1.0
+    }
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(prev + 1);
+        }
+    }
+    1.0 - row[b.len()] as f64 / len as f64
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::loxapp3::commands::{Command, CommandTarget, MutationBuilder};
+use crate::loxapp3::{is_valid_uuid, LoxoneApp3, LoxoneControl, LoxoneMutation, LoxoneUUID};
+use crate::time;
+use crate::ws::{CommandError, RequestError};
+
+/// Task of the `plannedTasks` or `pastTasks` global states.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    pub uuid: LoxoneUUID,
+    #[serde(default)]
+    pub name: String,
+    /// Control the command is sent to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub control_uuid: Option<LoxoneUUID>,
+    #[serde(default)]
+    pub command: LoxoneMutation,
+    /// Execution time, in seconds since 2009-01-01 Miniserver time.
+    pub time: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Task {
+    /// Parses the text of the `plannedTasks` or `pastTasks` global states.
+    pub fn parse(text: &str) -> Result<Vec<Self>, serde_json::Error> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new())
+        }
+        serde_json::from_str(text)
+    }
+
+    /// Returns the execution time in the time zone `tz` of the Miniserver.
+    pub fn time<Tz: TimeZone>(&self, tz: &Tz) -> Option<DateTime<Tz>> {
+        time::to_datetime(self.time, tz)
+    }
+
+    pub fn control<'a>(&self, loxapp3: &'a LoxoneApp3) -> Option<&'a LoxoneControl> {
+        self.control_uuid.as_ref().and_then(|uuid| loxapp3.controls.get(uuid))
+    }
+
+    /// Returns the command planning the given `mutation` to be sent to `control` at `timestamp`, in seconds since 2009-01-01 Miniserver time.
+    pub fn create_cmd(control: &LoxoneUUID, mutation: &LoxoneMutation, timestamp: i64) -> String {
+        // The mutation is already encoded, it is appended as is.
+        format!("{}/{}", MutationBuilder::new("jdev/sps/addcmd").arg(control).arg(timestamp).build(), mutation)
+    }
+
+    /// Returns the command planning the given typed `cmd` to be sent to `control` at `timestamp`, in seconds since 2009-01-01 Miniserver time.
+    ///
+    /// Secured controls are refused, planned commands cannot be authorized by the visualization password.
+    pub fn schedule_cmd<T: CommandTarget + ?Sized, C: Command>(control: &T, cmd: &C, timestamp: i64) -> Result<String, CommandError> {
+        if control.is_secured() {
+            return Err(CommandError::Secured(control.uuid().to_owned()))
+        }
+        if !is_valid_uuid(control.uuid()) {
+            return Err(RequestError::InvalidUUID(control.uuid().to_owned()).into())
+        }
+        let controller = control.controller();
+        let mutation = cmd.mutation(controller).ok_or_else(|| CommandError::UnsupportedController(controller.type_name().to_owned()))?;
+        Ok(Self::create_cmd(control.uuid(), &mutation, timestamp))
+    }
+
+    /// Returns the command deleting the planned task with the given UUID.
+    pub fn delete_cmd(uuid: &str) -> String {
+        MutationBuilder::new("jdev/sps/deletecmd").arg(uuid).build()
+    }
+}
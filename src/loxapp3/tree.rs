@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use crate::loxapp3::{LoxoneApp3, LoxoneController, LoxoneUUID};
+
+/// Control or sub-control within the control hierarchy.
+#[derive(Debug, Clone)]
+pub struct LoxoneControlNode<'a> {
+    pub uuid: &'a LoxoneUUID,
+    pub name: &'a str,
+    pub is_favorite: bool,
+    pub is_secured: bool,
+    pub controller: &'a LoxoneController,
+    /// Room of the control, inherited from the closest ancestor for sub-controls.
+    pub room: Option<&'a LoxoneUUID>,
+    /// Category of the control, inherited from the closest ancestor for sub-controls.
+    pub cat: Option<&'a LoxoneUUID>,
+    /// UUIDs of the ancestors, starting with the top-level control.
+    pub path: Vec<&'a LoxoneUUID>,
+}
+
+impl<'a> LoxoneControlNode<'a> {
+    /// Returns the UUID of the control the sub-control belongs to, `None` for top-level controls.
+    pub fn parent(&self) -> Option<&'a LoxoneUUID> {
+        self.path.last().copied()
+    }
+
+    /// Returns the number of ancestors, `0` for top-level controls.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Returns the sub-controls of this node, ordered by name.
+    pub fn children(&self) -> Vec<LoxoneControlNode<'a>> {
+        let mut path = self.path.clone();
+        path.push(self.uuid);
+        let mut children: Vec<_> = self.controller.sub_controls().into_iter().flat_map(|sub_controls| sub_controls.values()).map(|sub_control| LoxoneControlNode {
+            uuid: &sub_control.uuid_action,
+            name: &sub_control.name,
+            is_favorite: sub_control.is_favorite,
+            is_secured: sub_control.is_secured,
+            controller: &sub_control.controller,
+            room: self.room,
+            cat: self.cat,
+            path: path.clone(),
+        }).collect();
+        sort(&mut children);
+        children
+    }
+}
+
+/// Depth-first iterator over controls and their sub-controls.
+///
+/// Top-level controls and siblings are visited in name order, each control directly followed by its sub-controls.
+#[derive(Debug, Clone)]
+pub struct LoxoneControlIter<'a> {
+    stack: Vec<LoxoneControlNode<'a>>,
+}
+
+impl<'a> Iterator for LoxoneControlIter<'a> {
+    type Item = LoxoneControlNode<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children().into_iter().rev());
+        Some(node)
+    }
+}
+
+/// Parent/child tree over controls and sub-controls.
+#[derive(Debug, Clone)]
+pub struct LoxoneControlTree<'a> {
+    nodes: Vec<LoxoneControlNode<'a>>,
+    indices: HashMap<&'a str, usize>,
+    children: HashMap<&'a str, Vec<usize>>,
+}
+
+impl LoxoneApp3 {
+    /// Returns a depth-first iterator over all controls and sub-controls.
+    pub fn walk(&self) -> LoxoneControlIter<'_> {
+        let mut roots: Vec<_> = self.controls.values().map(|control| LoxoneControlNode {
+            uuid: &control.uuid_action,
+            name: &control.name,
+            is_favorite: control.is_favorite,
+            is_secured: control.is_secured,
+            controller: &control.controller,
+            room: control.room(),
+            cat: control.cat(),
+            path: Vec::new(),
+        }).collect();
+        sort(&mut roots);
+        roots.reverse();
+        LoxoneControlIter { stack: roots }
+    }
+
+    /// Builds the parent/child tree over all controls and sub-controls.
+    pub fn tree(&self) -> LoxoneControlTree<'_> {
+        LoxoneControlTree::new(self)
+    }
+}
+
+impl<'a> LoxoneControlTree<'a> {
+    pub fn new(loxapp3: &'a LoxoneApp3) -> Self {
+        let nodes: Vec<_> = loxapp3.walk().collect();
+        let indices = nodes.iter().enumerate().map(|(index, node)| (node.uuid.as_str(), index)).collect();
+        let mut children: HashMap<_, Vec<_>> = HashMap::new();
+        for (index, node) in nodes.iter().enumerate() {
+            if let Some(parent) = node.parent() {
+                children.entry(parent.as_str()).or_default().push(index);
+            }
+        }
+        Self { nodes, indices, children }
+    }
+
+    pub fn get(&self, uuid: &str) -> Option<&LoxoneControlNode<'a>> {
+        self.indices.get(uuid).map(|index| &self.nodes[*index])
+    }
+
+    /// Returns the control the given sub-control belongs to.
+    pub fn parent(&self, uuid: &str) -> Option<&LoxoneControlNode<'a>> {
+        self.get(uuid).and_then(LoxoneControlNode::parent).and_then(|parent| self.get(parent))
+    }
+
+    /// Returns the sub-controls of the given control, ordered by name.
+    pub fn children(&self, uuid: &str) -> impl Iterator<Item = &LoxoneControlNode<'a>> {
+        self.children.get(uuid).into_iter().flatten().map(move |index| &self.nodes[*index])
+    }
+
+    /// Returns the top-level controls, ordered by name.
+    pub fn roots(&self) -> impl Iterator<Item = &LoxoneControlNode<'a>> {
+        self.nodes.iter().filter(|node| node.path.is_empty())
+    }
+
+    /// Returns all nodes in depth-first order.
+    pub fn iter(&self) -> impl Iterator<Item = &LoxoneControlNode<'a>> {
+        self.nodes.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+fn sort(nodes: &mut [LoxoneControlNode<'_>]) {
+    nodes.sort_by(|a, b| a.name.cmp(b.name).then_with(|| a.uuid.cmp(b.uuid)));
+}
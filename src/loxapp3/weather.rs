@@ -0,0 +1,441 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+
+use crate::loxapp3::{LoxoneApp3, LoxoneMiniserverInfo, LoxoneWeatherEntry, LoxoneWeatherFieldType, LoxoneWeatherServer};
+use crate::time;
+
+/// Temperature unit configured on the Miniserver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+    Unknown(u8),
+}
+
+impl TemperatureUnit {
+    /// Converts the given temperature from degrees Celsius, unknown units are left as is.
+    pub fn from_celsius(&self, celsius: f64) -> f64 {
+        match self {
+            Self::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+            _ => celsius,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Fahrenheit => "°F",
+            _ => "°C",
+        }
+    }
+}
+
+impl From<u8> for TemperatureUnit {
+    fn from(val: u8) -> Self {
+        match val {
+            0 => Self::Celsius,
+            1 => Self::Fahrenheit,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<TemperatureUnit> for u8 {
+    fn from(unit: TemperatureUnit) -> Self {
+        match unit {
+            TemperatureUnit::Celsius => 0,
+            TemperatureUnit::Fahrenheit => 1,
+            TemperatureUnit::Unknown(val) => val,
+        }
+    }
+}
+
+impl LoxoneMiniserverInfo {
+    pub fn temperature_unit(&self) -> TemperatureUnit {
+        self.temp_unit.into()
+    }
+}
+
+/// Weather condition reported by the weather server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeatherType {
+    Clear,
+    MostlyClear,
+    PartlyCloudy,
+    MostlyCloudy,
+    Overcast,
+    Fog,
+    HighFog,
+    LightRain,
+    Rain,
+    HeavyRain,
+    Drizzle,
+    LightFreezingRain,
+    HeavyFreezingRain,
+    LightRainShowers,
+    HeavyRainShowers,
+    Thunderstorm,
+    HeavyThunderstorm,
+    LightSnow,
+    Snow,
+    HeavySnow,
+    LightSnowShowers,
+    HeavySnowShowers,
+    LightSleet,
+    Sleet,
+    HeavySleet,
+    LightSleetShowers,
+    HeavySleetShowers,
+    Unknown(i32),
+}
+
+impl WeatherType {
+    /// Returns the English description, the structure file may provide localized texts.
+    pub fn text(&self) -> &'static str {
+        match self {
+            Self::Clear => "Clear",
+            Self::MostlyClear => "Mostly clear",
+            Self::PartlyCloudy => "Partly cloudy",
+            Self::MostlyCloudy => "Mostly cloudy",
+            Self::Overcast => "Overcast",
+            Self::Fog => "Fog",
+            Self::HighFog => "High fog",
+            Self::LightRain => "Light rain",
+            Self::Rain => "Rain",
+            Self::HeavyRain => "Heavy rain",
+            Self::Drizzle => "Drizzle",
+            Self::LightFreezingRain => "Light freezing rain",
+            Self::HeavyFreezingRain => "Heavy freezing rain",
+            Self::LightRainShowers => "Light rain showers",
+            Self::HeavyRainShowers => "Heavy rain showers",
+            Self::Thunderstorm => "Thunderstorm",
+            Self::HeavyThunderstorm => "Heavy thunderstorm",
+            Self::LightSnow => "Light snow",
+            Self::Snow => "Snow",
+            Self::HeavySnow => "Heavy snow",
+            Self::LightSnowShowers => "Light snow showers",
+            Self::HeavySnowShowers => "Heavy snow showers",
+            Self::LightSleet => "Light sleet",
+            Self::Sleet => "Sleet",
+            Self::HeavySleet => "Heavy sleet",
+            Self::LightSleetShowers => "Light sleet showers",
+            Self::HeavySleetShowers => "Heavy sleet showers",
+            Self::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Returns the name of the icon depicting the weather condition.
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Clear | Self::MostlyClear => "clear",
+            Self::PartlyCloudy => "partly-cloudy",
+            Self::MostlyCloudy | Self::Overcast => "cloudy",
+            Self::Fog | Self::HighFog => "fog",
+            Self::LightRain | Self::Drizzle | Self::LightRainShowers => "light-rain",
+            Self::Rain | Self::HeavyRain | Self::HeavyRainShowers => "rain",
+            Self::LightFreezingRain | Self::HeavyFreezingRain => "freezing-rain",
+            Self::Thunderstorm | Self::HeavyThunderstorm => "thunderstorm",
+            Self::LightSnow | Self::Snow | Self::HeavySnow | Self::LightSnowShowers | Self::HeavySnowShowers => "snow",
+            Self::LightSleet | Self::Sleet | Self::HeavySleet | Self::LightSleetShowers | Self::HeavySleetShowers => "sleet",
+            Self::Unknown(_) => "unknown",
+        }
+    }
+}
+
+impl From<i32> for WeatherType {
+    fn from(val: i32) -> Self {
+        match val {
+            1 => Self::Clear,
+            2 => Self::MostlyClear,
+            3 => Self::PartlyCloudy,
+            4 => Self::MostlyCloudy,
+            5 => Self::Overcast,
+            6 => Self::Fog,
+            7 => Self::HighFog,
+            10 => Self::LightRain,
+            11 => Self::Rain,
+            12 => Self::HeavyRain,
+            13 => Self::Drizzle,
+            14 => Self::LightFreezingRain,
+            15 => Self::HeavyFreezingRain,
+            16 => Self::LightRainShowers,
+            17 => Self::HeavyRainShowers,
+            18 => Self::Thunderstorm,
+            19 => Self::HeavyThunderstorm,
+            20 => Self::LightSnow,
+            21 => Self::Snow,
+            22 => Self::HeavySnow,
+            23 => Self::LightSnowShowers,
+            24 => Self::HeavySnowShowers,
+            25 => Self::LightSleet,
+            26 => Self::Sleet,
+            27 => Self::HeavySleet,
+            28 => Self::LightSleetShowers,
+            29 => Self::HeavySleetShowers,
+            val => Self::Unknown(val),
+        }
+    }
+}
+
+impl From<WeatherType> for i32 {
+    fn from(weather_type: WeatherType) -> Self {
+        match weather_type {
+            WeatherType::Clear => 1,
+            WeatherType::MostlyClear => 2,
+            WeatherType::PartlyCloudy => 3,
+            WeatherType::MostlyCloudy => 4,
+            WeatherType::Overcast => 5,
+            WeatherType::Fog => 6,
+            WeatherType::HighFog => 7,
+            WeatherType::LightRain => 10,
+            WeatherType::Rain => 11,
+            WeatherType::HeavyRain => 12,
+            WeatherType::Drizzle => 13,
+            WeatherType::LightFreezingRain => 14,
+            WeatherType::HeavyFreezingRain => 15,
+            WeatherType::LightRainShowers => 16,
+            WeatherType::HeavyRainShowers => 17,
+            WeatherType::Thunderstorm => 18,
+            WeatherType::HeavyThunderstorm => 19,
+            WeatherType::LightSnow => 20,
+            WeatherType::Snow => 21,
+            WeatherType::HeavySnow => 22,
+            WeatherType::LightSnowShowers => 23,
+            WeatherType::HeavySnowShowers => 24,
+            WeatherType::LightSleet => 25,
+            WeatherType::Sleet => 26,
+            WeatherType::HeavySleet => 27,
+            WeatherType::LightSleetShowers => 28,
+            WeatherType::HeavySleetShowers => 29,
+            WeatherType::Unknown(val) => val,
+        }
+    }
+}
+
+/// Value measured or forecast by the weather server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeatherField {
+    Temperature,
+    PerceivedTemperature,
+    DewPoint,
+    RelativeHumidity,
+    WindSpeed,
+    WindDirection,
+    SolarRadiation,
+    Precipitation,
+    BarometricPressure,
+}
+
+impl WeatherField {
+    pub const ALL: [Self; 9] = [
+        Self::Temperature,
+        Self::PerceivedTemperature,
+        Self::DewPoint,
+        Self::RelativeHumidity,
+        Self::WindSpeed,
+        Self::WindDirection,
+        Self::SolarRadiation,
+        Self::Precipitation,
+        Self::BarometricPressure,
+    ];
+
+    /// Returns the field with the given name, ignoring case and separators.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name: String = name.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+        Self::ALL.iter().copied().find(|field| field.key().to_lowercase() == name)
+    }
+
+    /// Returns the key of the field in the weather server `format` mapping.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Temperature => "temperature",
+            Self::PerceivedTemperature => "perceivedTemperature",
+            Self::DewPoint => "dewPoint",
+            Self::RelativeHumidity => "relativeHumidity",
+            Self::WindSpeed => "windSpeed",
+            Self::WindDirection => "windDirection",
+            Self::SolarRadiation => "solarRadiation",
+            Self::Precipitation => "precipitation",
+            Self::BarometricPressure => "barometricPressure",
+        }
+    }
+
+    pub fn is_temperature(&self) -> bool {
+        matches!(self, Self::Temperature | Self::PerceivedTemperature | Self::DewPoint)
+    }
+
+    /// Returns the unit the weather server reports the field in.
+    fn default_unit(&self) -> &'static str {
+        match self {
+            Self::Temperature | Self::PerceivedTemperature | Self::DewPoint => "°C",
+            Self::RelativeHumidity => "%",
+            Self::WindSpeed => "km/h",
+            Self::WindDirection => "°",
+            Self::SolarRadiation => "W/m²",
+            Self::Precipitation => "mm",
+            Self::BarometricPressure => "hPa",
+        }
+    }
+}
+
+/// Hourly weather forecast entry, temperatures are converted to the Miniserver unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeatherForecastEntry {
+    pub time: DateTime<Utc>,
+    pub weather_type: WeatherType,
+    /// Wind direction in degrees.
+    pub wind_direction: i32,
+    pub solar_radiation: i32,
+    pub relative_humidity: i32,
+    pub temperature: f64,
+    pub perceived_temperature: f64,
+    pub dew_point: f64,
+    pub precipitation: f64,
+    pub wind_speed: f64,
+    pub barometric_pressure: f64,
+}
+
+impl WeatherForecastEntry {
+    /// Converts the given raw entry, its timestamp being local to the time zone `tz` of the Miniserver.
+    ///
+    /// Returns `None` if the timestamp is out of range.
+    pub fn new<Tz: TimeZone>(entry: &LoxoneWeatherEntry, unit: TemperatureUnit, tz: &Tz) -> Option<Self> {
+        Some(Self {
+            time: time::to_datetime(i64::from(entry.timestamp), tz)?.with_timezone(&Utc),
+            weather_type: entry.weather_type.into(),
+            wind_direction: entry.wind_direction,
+            solar_radiation: entry.solar_radiation,
+            relative_humidity: entry.relative_humidity,
+            temperature: unit.from_celsius(entry.temperature),
+            perceived_temperature: unit.from_celsius(entry.perceived_temperature),
+            dew_point: unit.from_celsius(entry.dew_point),
+            precipitation: entry.precipitation,
+            wind_speed: entry.wind_speed,
+            barometric_pressure: entry.barometric_pressure,
+        })
+    }
+
+    pub fn get(&self, field: WeatherField) -> f64 {
+        match field {
+            WeatherField::Temperature => self.temperature,
+            WeatherField::PerceivedTemperature => self.perceived_temperature,
+            WeatherField::DewPoint => self.dew_point,
+            WeatherField::RelativeHumidity => f64::from(self.relative_humidity),
+            WeatherField::WindSpeed => self.wind_speed,
+            WeatherField::WindDirection => f64::from(self.wind_direction),
+            WeatherField::SolarRadiation => f64::from(self.solar_radiation),
+            WeatherField::Precipitation => self.precipitation,
+            WeatherField::BarometricPressure => self.barometric_pressure,
+        }
+    }
+}
+
+/// Summary of the forecast entries of a single day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeatherDay {
+    pub date: NaiveDate,
+    pub min_temperature: f64,
+    pub max_temperature: f64,
+    /// Total precipitation.
+    pub precipitation: f64,
+    pub max_wind_speed: f64,
+}
+
+/// Weather forecast along with the weather server configuration of the structure file.
+#[derive(Debug, Clone)]
+pub struct WeatherForecast<'a> {
+    entries: Vec<WeatherForecastEntry>,
+    unit: TemperatureUnit,
+    server: Option<&'a LoxoneWeatherServer>,
+}
+
+impl LoxoneApp3 {
+    /// Converts the raw entries of the weather forecast state, dropping malformed entries.
+    ///
+    /// Timestamps are local to the time zone `tz` of the Miniserver.
+    pub fn weather_forecast<Tz: TimeZone>(&self, entries: &[LoxoneWeatherEntry], tz: &Tz) -> WeatherForecast<'_> {
+        WeatherForecast::new(entries, self.ms_info.temperature_unit(), tz, self.weather_server.as_ref())
+    }
+}
+
+impl<'a> WeatherForecast<'a> {
+    pub fn new<Tz: TimeZone>(entries: &[LoxoneWeatherEntry], unit: TemperatureUnit, tz: &Tz, server: Option<&'a LoxoneWeatherServer>) -> Self {
+        let mut entries: Vec<_> = entries.iter().filter_map(|entry| WeatherForecastEntry::new(entry, unit, tz)).collect();
+        entries.sort_by_key(|entry| entry.time);
+        Self { entries, unit, server }
+    }
+
+    /// Returns all entries, ordered by time.
+    pub fn entries(&self) -> &[WeatherForecastEntry] {
+        &self.entries
+    }
+
+    pub fn temperature_unit(&self) -> TemperatureUnit {
+        self.unit
+    }
+
+    /// Returns the entry of the hour containing `now`.
+    pub fn current(&self, now: DateTime<Utc>) -> Option<&WeatherForecastEntry> {
+        self.entries.iter().rev().find(|entry| entry.time <= now && entry.time + Duration::hours(1) > now)
+    }
+
+    /// Returns the entries of the next `hours` hours, starting with the current hour.
+    pub fn next_hours(&self, now: DateTime<Utc>, hours: u32) -> impl Iterator<Item = &WeatherForecastEntry> {
+        let end = now + Duration::hours(i64::from(hours));
+        self.entries.iter().filter(move |entry| entry.time + Duration::hours(1) > now && entry.time < end)
+    }
+
+    /// Returns the temperature range, total precipitation and maximum wind speed per day in the given time zone.
+    pub fn daily<Tz: TimeZone>(&self, tz: &Tz) -> Vec<WeatherDay> {
+        let mut days: Vec<WeatherDay> = Vec::new();
+        for entry in &self.entries {
+            let date = entry.time.with_timezone(tz).naive_local().date();
+            match days.last_mut() {
+                Some(day) if day.date == date => {
+                    day.min_temperature = day.min_temperature.min(entry.temperature);
+                    day.max_temperature = day.max_temperature.max(entry.temperature);
+                    day.precipitation += entry.precipitation;
+                    day.max_wind_speed = day.max_wind_speed.max(entry.wind_speed);
+                },
+                _ => days.push(WeatherDay {
+                    date,
+                    min_temperature: entry.temperature,
+                    max_temperature: entry.temperature,
+                    precipitation: entry.precipitation,
+                    max_wind_speed: entry.wind_speed,
+                }),
+            }
+        }
+        days
+    }
+
+    /// Returns the description of the given weather type, preferring the texts of the structure file.
+    pub fn text(&self, weather_type: WeatherType) -> &'a str {
+        self.server.and_then(|server| server.weather_type_texts.get(&i32::from(weather_type))).map_or(weather_type.text(), String::as_str)
+    }
+
+    /// Returns the fields described by the structure file along with their description, ordered by ID.
+    pub fn fields(&self) -> Vec<(WeatherField, &'a LoxoneWeatherFieldType)> {
+        let mut fields: Vec<_> = self.server.into_iter().flat_map(|server| server.weather_field_types.values())
+            .filter_map(|field_type| WeatherField::from_name(&field_type.name).map(|field| (field, field_type)))
+            .collect();
+        fields.sort_by_key(|(_, field_type)| field_type.id);
+        fields
+    }
+
+    /// Returns the unit of the given field, temperatures use the Miniserver unit.
+    pub fn unit(&self, field: WeatherField) -> &'a str {
+        if field.is_temperature() {
+            return self.unit.symbol()
+        }
+        self.field_type(field).map(|field_type| field_type.unit.as_str()).filter(|unit| !unit.is_empty()).unwrap_or_else(|| field.default_unit())
+    }
+
+    /// Returns the format of the given field, as found in the structure file.
+    pub fn format(&self, field: WeatherField) -> Option<&'a str> {
+        self.field_type(field).map(|field_type| field_type.format.as_str()).filter(|format| !format.is_empty())
+            .or_else(|| self.server.and_then(|server| server.format.get(field.key())).map(String::as_str))
+    }
+
+    fn field_type(&self, field: WeatherField) -> Option<&'a LoxoneWeatherFieldType> {
+        self.server.into_iter().flat_map(|server| server.weather_field_types.values()).find(|field_type| WeatherField::from_name(&field_type.name) == Some(field))
+    }
+}
@@ -1,9 +1,7 @@
-use tokio;
-
 //use std::collections::HashMap;
 //use tokio::stream::StreamExt;
 
-use loxone::{WebSocket, loxapp3::{controllers::LightControllerV2, LoxoneApp3}};
+use loxone::{WebSocket, loxapp3::{commands::LightControllerV2Command, controllers::LightControllerV2, LoxoneApp3}};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -22,7 +20,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("webSocket handshake has been successfully completed");
     println!("{:?}", resp);
 
-    let recv_loop = tokio::spawn(recv_loop);
+    let _recv_loop = tokio::spawn(recv_loop);
     println!("running recv loop on dedicated task");
 
     let reply = ws.key_exchange(&cert).await?;
@@ -33,15 +31,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let reply = ws.authenticate(jwt["token"].as_str().unwrap()).await?;
     println!("authenticated: {}", serde_json::to_string(&reply)?);
 
-    let (state, mut stream) = ws.enable_status_update(rx).await?;
+    let (_state, _stream) = ws.enable_status_update(rx).await?;
     println!("received initial state");
 
     let loxapp3: LoxoneApp3 = serde_json::from_str(&tokio::fs::read_to_string("loxapp3.json").await?)?;
 
-    let control_uuid = "149cfb32-033c-0a94-ffff403fb0c34b9e".to_string();
-    let control = &loxapp3.controls[&control_uuid];
-    ws.send_io_cmd(&control_uuid, LightControllerV2::plus()).await?;
-    println!("changed mood for {} in room {}", control.name, &loxapp3.rooms[control.room.as_ref().unwrap()].name);
+    let control = loxapp3.controls().in_room("Wohnzimmer").of_type::<LightControllerV2>().first().unwrap();
+    ws.send_command(&control, LightControllerV2Command::Plus).await?;
+    println!("changed mood for {} in room {}", control.name, control.room.unwrap().name);
 
     /*
     while let Some(event) = stream.next().await {
@@ -0,0 +1,69 @@
+//! Conversions between Loxone timestamps and standard datetimes.
+//!
+//! Loxone timestamps count the seconds since 2009-01-01 00:00:00 in the local time of the Miniserver.
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+/// Returns the Loxone epoch, 2009-01-01 00:00:00 Miniserver local time.
+pub fn epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd(2009, 1, 1).and_hms(0, 0, 0)
+}
+
+/// Converts the given Loxone timestamp to Miniserver local time, `None` if out of range.
+pub fn to_naive(timestamp: i64) -> Option<NaiveDateTime> {
+    epoch().checked_add_signed(Duration::milliseconds(timestamp.checked_mul(1000)?))
+}
+
+/// Converts the given Miniserver local time to a Loxone timestamp.
+pub fn from_naive(datetime: &NaiveDateTime) -> i64 {
+    (*datetime - epoch()).num_seconds()
+}
+
+/// Converts the given Loxone timestamp to a datetime in the time zone of the Miniserver.
+///
+/// Returns `None` if out of range, ambiguous local times resolve to the earliest instant.
+pub fn to_datetime<Tz: TimeZone>(timestamp: i64, tz: &Tz) -> Option<DateTime<Tz>> {
+    tz.from_local_datetime(&to_naive(timestamp)?).earliest()
+}
+
+/// Converts the given datetime to a Loxone timestamp in the time zone of the Miniserver.
+pub fn from_datetime<Tz: TimeZone, MsTz: TimeZone>(datetime: &DateTime<Tz>, tz: &MsTz) -> i64 {
+    from_naive(&datetime.with_timezone(tz).naive_local())
+}
+
+/// Parses the UTC offset reported by `jdev/cfg/timezoneoffset`, either in minutes or formatted as `±HH:MM`.
+pub fn parse_timezone_offset(text: &str) -> Option<FixedOffset> {
+    let text = text.trim();
+    let minutes = match text.find(':') {
+        Some(pos) => {
+            let sign = if text.starts_with('-') { -1 } else { 1 };
+            let hours: i32 = text[..pos].trim_start_matches(&['+', '-'][..]).parse().ok()?;
+            let minutes: i32 = text[pos + 1..].parse().ok()?;
+            sign * (hours * 60 + minutes)
+        },
+        None => text.parse().ok()?,
+    };
+    FixedOffset::east_opt(minutes.checked_mul(60)?)
+}
+
+/// Miniserver clock compared to the local host clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MiniserverTime {
+    /// Current time of the Miniserver.
+    pub time: DateTime<FixedOffset>,
+    /// Difference between the Miniserver and the local host clocks, positive if the Miniserver is ahead.
+    pub drift: Duration,
+}
+
+impl MiniserverTime {
+    /// Compares the time of day reported by the Miniserver to the given local host time.
+    ///
+    /// The Miniserver only reports the time of day, its date is the one closest to the local host time.
+    pub fn new(time: NaiveTime, offset: FixedOffset, now: DateTime<Utc>) -> Self {
+        let today = now.with_timezone(&offset).naive_local().date();
+        let utc_offset = Duration::seconds(i64::from(offset.local_minus_utc()));
+        let candidates = [today.pred(), today, today.succ()];
+        let utc = candidates.iter().map(|date| date.and_time(time) - utc_offset).min_by_key(|utc| (*utc - now.naive_utc()).num_milliseconds().abs()).unwrap_or_else(|| today.and_time(time) - utc_offset);
+        Self { time: DateTime::from_utc(utc, offset), drift: DateTime::<Utc>::from_utc(utc, Utc) - now }
+    }
+}
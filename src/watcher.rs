@@ -0,0 +1,80 @@
+use tokio::sync::mpsc;
+
+use crate::cache::LoxAPP3Cache;
+use crate::loxapp3::diff::LoxoneApp3Diff;
+use crate::loxapp3::index::LoxoneStateIndex;
+use crate::loxapp3::{LoxoneApp3, LoxoneState, LoxoneUUID};
+use crate::ws::{LoxAPP3RequestError, WebSocket};
+
+/// Keeps the structure file in sync with the configuration deployed on the Miniserver.
+///
+/// The watcher observes the `modifications` global state and reloads the structure file whenever it changes.
+pub struct StructureWatcher {
+    structure: LoxoneApp3,
+    index: LoxoneStateIndex,
+    modifications: Option<LoxoneState>,
+    cache: Option<LoxAPP3Cache>,
+    tx: mpsc::UnboundedSender<StructureChanged>,
+}
+
+/// Event published after the structure file has been reloaded.
+#[derive(Debug, Clone)]
+pub struct StructureChanged {
+    pub last_modified: String,
+    pub diff: LoxoneApp3Diff,
+}
+
+impl StructureWatcher {
+    /// Creates a watcher for the given structure file along with a receiver for structure change events.
+    ///
+    /// If a `cache` is given, reloaded structure files are read from and stored into it.
+    pub fn new(structure: LoxoneApp3, cache: Option<LoxAPP3Cache>) -> (Self, mpsc::UnboundedReceiver<StructureChanged>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let index = LoxoneStateIndex::new(&structure);
+        (Self { structure, index, modifications: None, cache, tx }, rx)
+    }
+
+    pub fn structure(&self) -> &LoxoneApp3 {
+        &self.structure
+    }
+
+    pub fn index(&self) -> &LoxoneStateIndex {
+        &self.index
+    }
+
+    /// Handles the given state update, reloading the structure file if the configuration has been re-deployed.
+    pub async fn update(&mut self, ws: &mut WebSocket, uuid: &LoxoneUUID, state: &LoxoneState) -> Result<Option<StructureChanged>, LoxAPP3RequestError> {
+        if self.observe(uuid, state) {
+            self.reload(ws).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Records the given state update, returning whether the structure file must be reloaded.
+    ///
+    /// The first value of the `modifications` global state is only recorded, a reload is needed whenever it changes afterwards.
+    pub fn observe(&mut self, uuid: &LoxoneUUID, state: &LoxoneState) -> bool {
+        if *uuid != self.structure.global_states.modifications {
+            return false
+        }
+        match self.modifications.replace(state.clone()) {
+            Some(modifications) => modifications != *state,
+            None => false,
+        }
+    }
+
+    /// Reloads the structure file, from the cache if it is still current, and rebuilds the derived indexes.
+    pub async fn reload(&mut self, ws: &mut WebSocket) -> Result<StructureChanged, LoxAPP3RequestError> {
+        let structure: LoxoneApp3 = match &self.cache {
+            Some(cache) => ws.get_loxapp3_cached(cache).await?,
+            None => ws.get_loxapp3().await?,
+        };
+        let event = StructureChanged { last_modified: structure.last_modified.to_owned(), diff: LoxoneApp3Diff::new(&self.structure, &structure) };
+        self.index = LoxoneStateIndex::new(&structure);
+        self.structure = structure;
+        // Publishing is best effort, the receiver may have been dropped.
+        let _ = self.tx.send(event.clone());
+        Ok(event)
+    }
+}
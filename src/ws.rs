@@ -1,5 +1,7 @@
 use byteorder::{LittleEndian, ReadBytesExt};
 
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+
 use crypto::digest::Digest;
 use crypto::mac::Mac;
 use crypto::hmac::Hmac;
@@ -20,14 +22,22 @@ use rsa::{PublicKey, RSAPublicKey};
 
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
-use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::io::{self, Cursor};
 
 use thiserror::Error;
 
 use tokio::{net::TcpStream, stream::Stream, sync::mpsc};
 use tokio_tungstenite::{connect_async, tungstenite, WebSocketStream};
 
-use crate::loxapp3::{LoxoneMutation, LoxoneUUID, LoxoneState, LoxoneDaytimerEntry, LoxoneWeatherEntry};
+use crate::cache::LoxAPP3Cache;
+use crate::diagnostics::{self, BusStatistics, ConfirmReboot, FirmwareVersion, Heap, LanStatistics, NetworkConfig, SystemStatus};
+use crate::events::{EventTable, EventTableKind};
+use crate::loxapp3::commands::{Command, CommandTarget, MutationBuilder};
+use crate::loxapp3::calendar::CalendarEntry;
+use crate::loxapp3::notifications::{MessageCenter, MessageCenterCommand};
+use crate::loxapp3::tasks::Task;
+use crate::loxapp3::{is_valid_uuid, LoxoneApp3, LoxoneControl, LoxoneMessage, LoxoneMutation, LoxoneUUID, LoxoneState};
+use crate::time::{self, MiniserverTime};
 
 /// WebSocket client for communicating with the Miniserver.
 pub struct WebSocket {
@@ -63,29 +73,12 @@ enum MessageType {
 enum Message {
     Text(String),
     BinaryText(String),
-    BinaryFile(Vec<u8>),
+    BinaryFile(#[allow(dead_code)] Vec<u8>),
     EventTable(EventTable),
     OutOfServiceIndicator,
     KeepAlive,
 }
 
-#[derive(Debug)]
-struct ValueEvent(LoxoneUUID, f64);
-#[derive(Debug)]
-struct TextEvent(LoxoneUUID, LoxoneUUID, String);
-#[derive(Debug)]
-struct DaytimerEvent(LoxoneUUID, f64, Vec<LoxoneDaytimerEntry>);
-#[derive(Debug)]
-struct WeatherEvent(LoxoneUUID, u32, Vec<LoxoneWeatherEntry>);
-
-#[derive(Debug)]
-enum EventTable {
-    ValueEvents(Vec<ValueEvent>),
-    TextEvents(Vec<TextEvent>),
-    DaytimerEvents(Vec<DaytimerEvent>),
-    WeatherEvents(Vec<WeatherEvent>),
-}
-
 #[derive(Error, Debug)]
 pub enum X509CertError {
     #[error("pem error")]
@@ -128,6 +121,16 @@ pub enum RequestError {
     JsonMissingField(&'static str),
     #[error("invalid reply status code")]
     InvalidStatusCode(String),
+    #[error("invalid uuid {0:?}")]
+    InvalidUUID(String),
+    #[error("key decode error")]
+    KeyDecode(#[from] hex::FromHexError),
+    #[error("invalid reply value {0:?}")]
+    InvalidValue(String),
+    #[error("unsupported hash algorithm {0:?}")]
+    UnsupportedHashAlg(String),
+    #[error("invalid date")]
+    InvalidDate(#[from] chrono::ParseError),
 }
 
 #[derive(Error, Debug)]
@@ -178,6 +181,24 @@ pub enum LoxAPP3RequestError {
     InvalidMessageType,
     #[error("invalid json reply")]
     JsonDeserialize(#[from] serde_json::Error),
+    #[error("version request error")]
+    VersionRequest(#[from] RequestError),
+    #[error("cache error")]
+    Cache(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum CommandError {
+    #[error("command does not apply to {0} controls")]
+    UnsupportedController(String),
+    #[error("{0} controls are not central controls")]
+    NotCentral(String),
+    #[error("unknown control {0}")]
+    UnknownControl(LoxoneUUID),
+    #[error("control {0} is secured, commands must be sent through the secured path")]
+    Secured(LoxoneUUID),
+    #[error("command request error")]
+    Request(#[from] RequestError),
 }
 
 impl WebSocket {
@@ -214,7 +235,7 @@ impl WebSocket {
     /// Authenticates with the given token.
     pub async fn authenticate(&mut self, token: &str) -> Result<serde_json::Map<String, serde_json::Value>, AuthenticationError> {
         let key = &self.get_key().await?;
-        let hash = hash_token(token, &hex::decode(&key)?, "SHA1");
+        let hash = hash_token(token, &hex::decode(key)?, "SHA1")?;
         let payload: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(&base64::decode(token.split('.').nth(1).ok_or(AuthenticationError::JwtBadFormat)?)?)?;
         match self.send_recv_enc(&format!("authwithtoken/{}/{}", hex::encode(hash), payload["user"].as_str().ok_or(RequestError::JsonMissingField("LL.value.user"))?)).await? {
             Message::Text(reply) => {
@@ -257,6 +278,20 @@ impl WebSocket {
         }
     }
 
+    async fn get_visu_salt(&mut self, user: &str) -> Result<serde_json::Map<String, serde_json::Value>, RequestError> {
+        match self.send_recv(&MutationBuilder::new("jdev/sys/getvisusalt").arg(user).build()).await? {
+            Message::Text(reply) => {
+                let reply_json: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&reply)?;
+                match reply_json["LL"]["code"].as_str().or_else(|| reply_json["LL"]["Code"].as_str()) {
+                    Some("200") => Ok(reply_json["LL"]["value"].as_object().ok_or(RequestError::JsonMissingField("LL.value"))?.to_owned()),
+                    Some(status_code) => Err(RequestError::InvalidStatusCode(status_code.to_owned())),
+                    None => Err(RequestError::JsonMissingField("LL.code"))
+                }
+            },
+            _reply => Err(RequestError::InvalidMessageType)
+        }
+    }
+
     /// Returns the JSON Web Token for the given authentication credentials.
     pub async fn get_jwt(&mut self, user: &str, password: &str, permission: u8, uuid: &str, info: &str) -> Result<serde_json::Map<String, serde_json::Value>, JwtRequestError> {
         let auth = self.get_key_salt(user).await?;
@@ -266,7 +301,7 @@ impl WebSocket {
             &hex::decode(auth["key"].as_str().ok_or(RequestError::JsonMissingField("LL.value.key"))?)?,
             auth["salt"].as_str().ok_or(RequestError::JsonMissingField("LL.value.salt"))?,
             auth["hashAlg"].as_str().ok_or(RequestError::JsonMissingField("LL.value.hashAlg"))?
-        );
+        )?;
 
         match self.send_recv_enc(&format!("jdev/sys/getjwt/{}/{}/{}/{}/{}", hex::encode(hash), user, permission, uuid, info)).await? {
             Message::Text(reply) => {
@@ -281,13 +316,34 @@ impl WebSocket {
         }
     }
 
-    /// Returns the LoxAPP3 structure file.
+    /// Returns the LoxAPP3 structure file, downloading it unconditionally.
+    ///
+    /// Prefer [`get_loxapp3_cached`](Self::get_loxapp3_cached) when connecting, the structure file may be several megabytes.
     pub async fn get_loxapp3<T: for<'de> serde::Deserialize<'de>>(&mut self) -> Result<T, LoxAPP3RequestError> {
+        let reply = self.get_loxapp3_json().await?;
+        Ok(serde_json::from_str(&reply)?)
+    }
+
+    /// Returns the LoxAPP3 structure file from the given `cache`.
+    ///
+    /// The structure file is only downloaded if the cached version differs from the `LoxAPPversion3` reported by the Miniserver.
+    /// This is the entry point for loading the structure file on connect, pass the same cache to [`StructureWatcher::new`](crate::StructureWatcher::new) to keep it current.
+    pub async fn get_loxapp3_cached<T: for<'de> serde::Deserialize<'de>>(&mut self, cache: &LoxAPP3Cache) -> Result<T, LoxAPP3RequestError> {
+        let last_modified = self.get_loxapp3_timestamp().await?;
+        if let Some(reply) = cache.load(&last_modified).await? {
+            if let Ok(reply_json) = serde_json::from_str(&reply) {
+                return Ok(reply_json)
+            }
+        }
+        let reply = self.get_loxapp3_json().await?;
+        let reply_json = serde_json::from_str(&reply)?;
+        cache.store(&last_modified, &reply).await?;
+        Ok(reply_json)
+    }
+
+    async fn get_loxapp3_json(&mut self) -> Result<String, LoxAPP3RequestError> {
         match self.send_recv("data/LoxAPP3.json").await? {
-            Message::BinaryText(reply) => {
-                let reply_json = serde_json::from_str(&reply)?;
-                Ok(reply_json)
-            },
+            Message::BinaryText(reply) => Ok(reply),
             _reply => Err(LoxAPP3RequestError::InvalidMessageType)
         }
     }
@@ -297,7 +353,6 @@ impl WebSocket {
         match self.send_recv("jdev/sps/LoxAPPversion3").await? {
             Message::Text(reply) => {
                 let reply_json: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&reply)?;
-                assert_eq!(reply_json["LL"]["Code"].as_str(), Some("200"));
                 match reply_json["LL"]["Code"].as_str() {
                     Some("200") => Ok(reply_json["LL"]["value"].as_str().ok_or(RequestError::JsonMissingField("LL.value"))?.to_owned()),
                     Some(status_code) => Err(RequestError::InvalidStatusCode(status_code.to_owned())),
@@ -308,6 +363,159 @@ impl WebSocket {
         }
     }
 
+    /// Returns the current time of the Miniserver along with its drift against the local host clock.
+    pub async fn get_miniserver_time(&mut self) -> Result<MiniserverTime, RequestError> {
+        let offset = self.get_value("jdev/cfg/timezoneoffset").await?;
+        let offset = time::parse_timezone_offset(&offset).ok_or(RequestError::InvalidValue(offset))?;
+        let sent = Utc::now();
+        let reply = self.get_value("jdev/sys/time").await?;
+        // The reply is assumed to be taken halfway through the round trip.
+        let now = sent + (Utc::now() - sent) / 2;
+        let time = NaiveTime::parse_from_str(reply.trim(), "%H:%M:%S").map_err(|_| RequestError::InvalidValue(reply))?;
+        Ok(MiniserverTime::new(time, offset, now))
+    }
+
+    /// Returns the entries of the given message center.
+    pub async fn get_message_center(&mut self, message_center: &LoxoneMessage) -> Result<MessageCenter, RequestError> {
+        if !is_valid_uuid(&message_center.uuid_action) {
+            return Err(RequestError::InvalidUUID(message_center.uuid_action.to_owned()))
+        }
+        let reply = self.get_value(&format!("jdev/sps/io/{}/getEntries/2", message_center.uuid_action)).await?;
+        Ok(MessageCenter::parse(&reply)?)
+    }
+
+    /// Sends the given `cmd` to the given message center.
+    pub async fn send_message_center_command(&mut self, message_center: &LoxoneMessage, cmd: MessageCenterCommand) -> Result<(), RequestError> {
+        if !is_valid_uuid(&message_center.uuid_action) {
+            return Err(RequestError::InvalidUUID(message_center.uuid_action.to_owned()))
+        }
+        self.get_value(&format!("jdev/sps/io/{}/{}", message_center.uuid_action, cmd.mutation())).await?;
+        Ok(())
+    }
+
+    /// Returns the calendar entries activating operating modes.
+    pub async fn get_calendar_entries(&mut self) -> Result<Vec<CalendarEntry>, RequestError> {
+        let reply = self.get_value("jdev/sps/calendargetentries").await?;
+        Ok(CalendarEntry::parse(&reply)?)
+    }
+
+    pub async fn create_calendar_entry(&mut self, entry: &CalendarEntry) -> Result<(), RequestError> {
+        self.get_value(&entry.create_cmd()?).await?;
+        Ok(())
+    }
+
+    /// Updates the calendar entry with the UUID of the given `entry`.
+    pub async fn update_calendar_entry(&mut self, entry: &CalendarEntry) -> Result<(), RequestError> {
+        self.get_value(&entry.update_cmd()?).await?;
+        Ok(())
+    }
+
+    pub async fn delete_calendar_entry(&mut self, uuid: &str) -> Result<(), RequestError> {
+        if !is_valid_uuid(uuid) {
+            return Err(RequestError::InvalidUUID(uuid.to_owned()))
+        }
+        self.get_value(&CalendarEntry::delete_cmd(uuid)).await?;
+        Ok(())
+    }
+
+    /// Plans the given typed `cmd` to be sent to the given `control` at the given time, `tz` being the time zone of the Miniserver.
+    ///
+    /// Secured controls are refused, see [`Task::schedule_cmd`].
+    pub async fn schedule_command<T: CommandTarget + ?Sized, C: Command, Tz: TimeZone, MsTz: TimeZone>(&mut self, control: &T, cmd: C, at: &DateTime<Tz>, tz: &MsTz) -> Result<(), CommandError> {
+        self.get_value(&Task::schedule_cmd(control, &cmd, time::from_datetime(at, tz))?).await?;
+        Ok(())
+    }
+
+    /// Deletes the planned task with the given UUID.
+    pub async fn delete_task(&mut self, uuid: &str) -> Result<(), RequestError> {
+        if !is_valid_uuid(uuid) {
+            return Err(RequestError::InvalidUUID(uuid.to_owned()))
+        }
+        self.get_value(&Task::delete_cmd(uuid)).await?;
+        Ok(())
+    }
+
+    /// Returns the firmware version of the Miniserver.
+    pub async fn get_version(&mut self) -> Result<FirmwareVersion, RequestError> {
+        self.get_parsed("jdev/cfg/version", |value| value.parse().ok()).await
+    }
+
+    /// Returns the network configuration of the Miniserver.
+    pub async fn get_network_config(&mut self) -> Result<NetworkConfig, RequestError> {
+        Ok(NetworkConfig {
+            ip: self.get_value("jdev/cfg/ip").await?,
+            mask: self.get_value("jdev/cfg/mask").await?,
+            gateway: self.get_value("jdev/cfg/gateway").await?,
+            dns1: self.get_value("jdev/cfg/dns1").await?,
+            dns2: self.get_value("jdev/cfg/dns2").await?,
+            mac: self.get_value("jdev/cfg/mac").await?,
+            dhcp: self.get_parsed("jdev/cfg/dhcp", diagnostics::parse_flag).await?,
+        })
+    }
+
+    /// Returns the load of the Miniserver.
+    pub async fn get_system_status(&mut self) -> Result<SystemStatus, RequestError> {
+        Ok(SystemStatus {
+            tasks: self.get_parsed("jdev/sys/numtasks", diagnostics::parse_counter).await?,
+            cpu: self.get_parsed("jdev/sys/cpu", diagnostics::parse_percent).await?,
+            heap: self.get_parsed("jdev/sys/heap", Heap::parse).await?,
+            context_switches: self.get_parsed("jdev/sys/contextswitches", diagnostics::parse_counter).await?,
+        })
+    }
+
+    /// Tests the SD card of the Miniserver, returning the test report.
+    pub async fn test_sd_card(&mut self) -> Result<String, RequestError> {
+        self.get_value("jdev/sys/sdtest").await
+    }
+
+    pub async fn get_lan_statistics(&mut self) -> Result<LanStatistics, RequestError> {
+        Ok(LanStatistics {
+            packets_sent: self.get_parsed("jdev/lan/txp", diagnostics::parse_counter).await?,
+            send_errors: self.get_parsed("jdev/lan/txe", diagnostics::parse_counter).await?,
+            packets_received: self.get_parsed("jdev/lan/rxp", diagnostics::parse_counter).await?,
+        })
+    }
+
+    pub async fn get_bus_statistics(&mut self) -> Result<BusStatistics, RequestError> {
+        Ok(BusStatistics {
+            packets_sent: self.get_parsed("jdev/bus/packetssent", diagnostics::parse_counter).await?,
+            packets_received: self.get_parsed("jdev/bus/packetsreceived", diagnostics::parse_counter).await?,
+            receive_errors: self.get_parsed("jdev/bus/receiveerrors", diagnostics::parse_counter).await?,
+            frame_errors: self.get_parsed("jdev/bus/frameerrors", diagnostics::parse_counter).await?,
+            overruns: self.get_parsed("jdev/bus/overruns", diagnostics::parse_counter).await?,
+        })
+    }
+
+    /// Reboots the Miniserver, the connection is closed by the Miniserver afterwards.
+    pub async fn reboot(&mut self, _confirm: ConfirmReboot) -> Result<(), RequestError> {
+        self.get_value("jdev/sys/reboot").await?;
+        Ok(())
+    }
+
+    async fn get_parsed<T, F: FnOnce(&str) -> Option<T>>(&mut self, cmd: &str, parse: F) -> Result<T, RequestError> {
+        let value = self.get_value(cmd).await?;
+        parse(&value).ok_or(RequestError::InvalidValue(value))
+    }
+
+    async fn get_value(&mut self, cmd: &str) -> Result<String, RequestError> {
+        match self.send_recv(cmd).await? {
+            Message::Text(reply) => {
+                let reply_json: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&reply)?;
+                match reply_json["LL"]["Code"].as_str().or_else(|| reply_json["LL"]["code"].as_str()) {
+                    Some("200") => match &reply_json["LL"]["value"] {
+                        serde_json::Value::String(value) => Ok(value.to_owned()),
+                        serde_json::Value::Number(value) => Ok(value.to_string()),
+                        value @ serde_json::Value::Object(_) | value @ serde_json::Value::Array(_) => Ok(value.to_string()),
+                        _ => Err(RequestError::JsonMissingField("LL.value")),
+                    },
+                    Some(status_code) => Err(RequestError::InvalidStatusCode(status_code.to_owned())),
+                    None => Err(RequestError::JsonMissingField("LL.Code"))
+                }
+            },
+            _reply => Err(RequestError::InvalidMessageType)
+        }
+    }
+
     /// Enables status updates.
     pub async fn enable_status_update(&mut self, mut rx: EventReceiver) -> Result<(HashMap<LoxoneUUID, LoxoneState>, impl Stream<Item=(LoxoneUUID, LoxoneState)>), RequestError> {
         match self.send_recv("jdev/sps/enablebinstatusupdate").await? {
@@ -316,8 +524,8 @@ impl WebSocket {
                 match reply_json["LL"]["Code"].as_str() {
                     Some("200") => {
                         assert_eq!(reply_json["LL"]["value"].as_str().ok_or(RequestError::JsonMissingField("LL.value"))?, "1");
-                        let initial_state = rx.rx.by_ref().take(4).map(|event_table| event_table.into()).concat().await;
-                        let stream = rx.rx.flat_map(|event_table|stream::iter::<HashMap<LoxoneUUID, LoxoneState>>(event_table.into()));
+                        let initial_state = rx.rx.by_ref().take(4).map(|event_table| HashMap::from(&event_table)).concat().await;
+                        let stream = rx.rx.flat_map(stream::iter);
                         Ok((initial_state, stream))
                     },
                     Some(status_code) => Err(RequestError::InvalidStatusCode(status_code.to_owned())),
@@ -329,15 +537,17 @@ impl WebSocket {
     }
 
     /// Sends the given `cmd` mutation to the given `control` UUID.
+    ///
+    /// The mutation is sent as-is, arguments must have been encoded, see [`MutationBuilder`].
     pub async fn send_io_cmd(&mut self, control: &LoxoneUUID, cmd: LoxoneMutation) -> Result<(), RequestError> {
+        if !is_valid_uuid(control) {
+            return Err(RequestError::InvalidUUID(control.to_owned()))
+        }
         match self.send_recv(&format!("jdev/sps/io/{}/{}", control, cmd)).await? {
             Message::Text(reply) => {
                 let reply_json: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&reply)?;
-                match reply_json["LL"]["Code"].as_str() {
-                    Some("200") => {
-                        assert_eq!(reply_json["LL"]["value"].as_str().ok_or(RequestError::JsonMissingField("LL.value"))?, "1");
-                        Ok(())
-                    },
+                match reply_json["LL"]["Code"].as_str().or_else(|| reply_json["LL"]["code"].as_str()) {
+                    Some("200") => Ok(()),
                     Some(status_code) => Err(RequestError::InvalidStatusCode(status_code.to_owned())),
                     None => Err(RequestError::JsonMissingField("LL.Code"))
                 }
@@ -346,6 +556,74 @@ impl WebSocket {
         }
     }
 
+    /// Sends the given `cmd` mutation to the given secured `control` UUID, authorized by the visualization password of `user`.
+    pub async fn send_secured_io_cmd(&mut self, user: &str, visu_password: &str, control: &LoxoneUUID, cmd: LoxoneMutation) -> Result<(), RequestError> {
+        if !is_valid_uuid(control) {
+            return Err(RequestError::InvalidUUID(control.to_owned()))
+        }
+        let visu_salt = self.get_visu_salt(user).await?;
+        let hash = hash_visu_pwd(
+            visu_password,
+            &hex::decode(visu_salt["key"].as_str().ok_or(RequestError::JsonMissingField("LL.value.key"))?)?,
+            visu_salt["salt"].as_str().ok_or(RequestError::JsonMissingField("LL.value.salt"))?,
+            visu_salt["hashAlg"].as_str().unwrap_or("SHA1")
+        )?;
+        match self.send_recv(&format!("jdev/sps/ios/{}/{}/{}", hex::encode(hash), control, cmd)).await? {
+            Message::Text(reply) => {
+                let reply_json: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&reply)?;
+                match reply_json["LL"]["Code"].as_str().or_else(|| reply_json["LL"]["code"].as_str()) {
+                    Some("200") => Ok(()),
+                    Some(status_code) => Err(RequestError::InvalidStatusCode(status_code.to_owned())),
+                    None => Err(RequestError::JsonMissingField("LL.Code"))
+                }
+            },
+            _reply => Err(RequestError::InvalidMessageType)
+        }
+    }
+
+    /// Sends the given typed `cmd` to the given `control`, refusing commands that do not apply to its controller type.
+    ///
+    /// Secured controls are refused, see [`send_secured_command`](Self::send_secured_command).
+    pub async fn send_command<T: CommandTarget + ?Sized, C: Command>(&mut self, control: &T, cmd: C) -> Result<(), CommandError> {
+        if control.is_secured() {
+            return Err(CommandError::Secured(control.uuid().to_owned()))
+        }
+        let controller = control.controller();
+        let mutation = cmd.mutation(controller).ok_or_else(|| CommandError::UnsupportedController(controller.type_name().to_owned()))?;
+        Ok(self.send_io_cmd(control.uuid(), mutation).await?)
+    }
+
+    /// Sends the given typed `cmd` to the given secured `control`, authorized by the visualization password of `user`.
+    pub async fn send_secured_command<T: CommandTarget + ?Sized, C: Command>(&mut self, user: &str, visu_password: &str, control: &T, cmd: C) -> Result<(), CommandError> {
+        let controller = control.controller();
+        let mutation = cmd.mutation(controller).ok_or_else(|| CommandError::UnsupportedController(controller.type_name().to_owned()))?;
+        Ok(self.send_secured_io_cmd(user, visu_password, control.uuid(), mutation).await?)
+    }
+
+    /// Sends the given typed `cmd` to each control managed by the given `central` control.
+    ///
+    /// The command is checked against all controls before any of them is sent, secured controls are refused, see [`send_secured_central_command`](Self::send_secured_central_command).
+    pub async fn send_central_command<T: CommandTarget + ?Sized, C: Command>(&mut self, loxapp3: &LoxoneApp3, central: &T, cmd: C) -> Result<(), CommandError> {
+        let mutations = central_mutations(loxapp3, central, &cmd)?;
+        if let Some((control, _)) = mutations.iter().find(|(control, _)| control.is_secured) {
+            return Err(CommandError::Secured(control.uuid_action.to_owned()))
+        }
+        for (control, mutation) in mutations {
+            self.send_io_cmd(&control.uuid_action, mutation).await?;
+        }
+        Ok(())
+    }
+
+    /// Sends the given typed `cmd` to each control managed by the given `central` control, authorized by the visualization password of `user`.
+    ///
+    /// The command is checked against all controls before any of them is sent.
+    pub async fn send_secured_central_command<T: CommandTarget + ?Sized, C: Command>(&mut self, user: &str, visu_password: &str, loxapp3: &LoxoneApp3, central: &T, cmd: C) -> Result<(), CommandError> {
+        for (control, mutation) in central_mutations(loxapp3, central, &cmd)? {
+            self.send_secured_io_cmd(user, visu_password, &control.uuid_action, mutation).await?;
+        }
+        Ok(())
+    }
+
     async fn send_recv(&mut self, cmd: &str) -> Result<Message, tungstenite::Error> {
         self.sink.send(tungstenite::Message::from(cmd)).await?;
         self.recv().await
@@ -353,7 +631,7 @@ impl WebSocket {
 
     async fn send_recv_enc(&mut self, cmd: &str) -> Result<Message, tungstenite::Error> {
         let session = self.session.as_ref().ok_or(tungstenite::Error::from(io::Error::from(io::ErrorKind::PermissionDenied)))?;
-        let encrypted_cmd = encrypt_cmd_ws("enc", &cmd, session).or(Err(tungstenite::Error::from(io::Error::new(io::ErrorKind::InvalidInput, cmd))))?;
+        let encrypted_cmd = encrypt_cmd_ws("enc", cmd, session).or(Err(tungstenite::Error::from(io::Error::new(io::ErrorKind::InvalidInput, cmd))))?;
         self.send_recv(&encrypted_cmd).await
     }
 
@@ -423,18 +701,18 @@ impl TryFrom<u8> for MessageType {
     }
 }
 
-impl Into<HashMap<LoxoneUUID, LoxoneState>> for EventTable {
-    fn into(self) -> HashMap<LoxoneUUID, LoxoneState> {
-        match self { // TODO
-            Self::ValueEvents(events) => events.into_iter().map(|event| (event.0, LoxoneState::Value(event.1))).collect(),
-            Self::TextEvents(events) => events.into_iter().map(|event| (event.0, LoxoneState::Text(event.2, event.1))).collect(),
-            Self::DaytimerEvents(events) => events.into_iter().map(|event| (event.0, LoxoneState::Daytimer(event.2, event.1))).collect(),
-            Self::WeatherEvents(events) => events.into_iter().map(|event| (event.0, LoxoneState::Weather(event.2, event.1))).collect(),
-        }
-    }
+/// Returns the mutation of the given `cmd` for each control managed by the given `central` control.
+fn central_mutations<'a, T: CommandTarget + ?Sized, C: Command>(loxapp3: &'a LoxoneApp3, central: &T, cmd: &C) -> Result<Vec<(&'a LoxoneControl, LoxoneMutation)>, CommandError> {
+    let controller = central.controller();
+    let uuids = controller.central_controls().ok_or_else(|| CommandError::NotCentral(controller.type_name().to_owned()))?;
+    uuids.into_iter().map(|uuid| {
+        let control = loxapp3.controls.get(uuid).ok_or_else(|| CommandError::UnknownControl(uuid.to_owned()))?;
+        let mutation = cmd.mutation(&control.controller).ok_or_else(|| CommandError::UnsupportedController(control.controller.type_name().to_owned()))?;
+        Ok((control, mutation))
+    }).collect()
 }
 
-fn hash_pwd(user: &str, pwd: &str, key: &[u8], salt: &str, hash_alg: &str) -> Vec<u8> {
+fn hash_pwd(user: &str, pwd: &str, key: &[u8], salt: &str, hash_alg: &str) -> Result<Vec<u8>, RequestError> {
     match hash_alg {
         "SHA1" => {
             let mut hasher = Sha1::new();
@@ -445,7 +723,7 @@ fn hash_pwd(user: &str, pwd: &str, key: &[u8], salt: &str, hash_alg: &str) -> Ve
             mac.input(format!("{}:{}", user, password_hash).as_bytes());
 
             let mac_result = mac.result();
-            mac_result.code().to_vec()
+            Ok(mac_result.code().to_vec())
         }
         "SHA256" => {
             let mut hasher = Sha256::new();
@@ -456,29 +734,49 @@ fn hash_pwd(user: &str, pwd: &str, key: &[u8], salt: &str, hash_alg: &str) -> Ve
             mac.input(format!("{}:{}", user, password_hash).as_bytes());
 
             let mac_result = mac.result();
-            mac_result.code().to_vec()
+            Ok(mac_result.code().to_vec())
         },
-        _ => panic!("Can only use SHA1 and SHA256 here.")
+        hash_alg => Err(RequestError::UnsupportedHashAlg(hash_alg.to_owned()))
     }
 }
 
-fn hash_token(token: &str, key: &[u8], hash_alg: &str) -> Vec<u8> {
+/// Returns the hash of the visualization password `pwd`, as sent with commands to secured controls.
+///
+/// The `key`, `salt` and `hash_alg` are those returned by `jdev/sys/getvisusalt`.
+pub fn hash_visu_pwd(pwd: &str, key: &[u8], salt: &str, hash_alg: &str) -> Result<Vec<u8>, RequestError> {
+    let password_hash = match hash_alg {
+        "SHA1" => {
+            let mut hasher = Sha1::new();
+            hasher.input_str(format!("{}:{}", pwd, salt).as_str());
+            hasher.result_str().to_uppercase()
+        },
+        "SHA256" => {
+            let mut hasher = Sha256::new();
+            hasher.input_str(format!("{}:{}", pwd, salt).as_str());
+            hasher.result_str().to_uppercase()
+        },
+        hash_alg => return Err(RequestError::UnsupportedHashAlg(hash_alg.to_owned()))
+    };
+    hash_token(&password_hash, key, hash_alg)
+}
+
+fn hash_token(token: &str, key: &[u8], hash_alg: &str) -> Result<Vec<u8>, RequestError> {
     match hash_alg {
         "SHA1" => {
             let mut mac = Hmac::<Sha1>::new(Sha1::new(), key);
             mac.input(token.as_bytes());
 
             let mac_result = mac.result();
-            mac_result.code().to_vec()
+            Ok(mac_result.code().to_vec())
         }
         "SHA256" => {
             let mut mac = Hmac::<Sha256>::new(Sha256::new(), key);
             mac.input(token.as_bytes());
 
             let mac_result = mac.result();
-            mac_result.code().to_vec()
+            Ok(mac_result.code().to_vec())
         },
-        _ => panic!("Can only use SHA1 and SHA256 here.")
+        hash_alg => Err(RequestError::UnsupportedHashAlg(hash_alg.to_owned()))
     }
 }
 
@@ -493,7 +791,7 @@ fn encrypt_cmd(cmd: &str, session: &Session) -> Result<Vec<u8>, symmetriccipher:
 
     loop {
         let result = encryptor.encrypt(&mut read_buffer, &mut write_buffer, true)?;
-        final_result.extend(write_buffer.take_read_buffer().take_remaining().iter().map(|&i| i));
+        final_result.extend(write_buffer.take_read_buffer().take_remaining().iter().copied());
 
         match result {
             BufferResult::BufferUnderflow => break,
@@ -516,7 +814,7 @@ fn parse_cert(cert: &str) -> Result<RSAPublicKey, X509CertError> {
     match asn1_blocks.first() {
         Some(simple_asn1::ASN1Block::Sequence(_ofs, seq_blocks)) =>
             match seq_blocks.last() {
-                Some(simple_asn1::ASN1Block::BitString(_ofs, _len, der)) => rsa::RSAPublicKey::from_pkcs1(der).map_err(|err| X509CertError::PKCS1(err)),
+                Some(simple_asn1::ASN1Block::BitString(_ofs, _len, der)) => rsa::RSAPublicKey::from_pkcs1(der).map_err(X509CertError::PKCS1),
                 _ => Err(X509CertError::ASN1MissingBlock)
             },
         _ => Err(X509CertError::ASN1MissingBlock)
@@ -550,7 +848,7 @@ fn parse_msg_header(mut header: &[u8]) -> (MessageType, Option<usize>) {
 
 fn parse_msg_len(header_msg: tungstenite::Message) -> u64 {
     let mut header = Cursor::new(header_msg.into_data());
-    header.read_u32::<LittleEndian>().unwrap().try_into().unwrap()
+    header.read_u32::<LittleEndian>().unwrap().into()
 }
 
 async fn parse_msg_body<S: StreamExt<Item=tungstenite::Message> + Unpin>(msg_type: MessageType, msg_len: u64, stream: &mut S) -> Message {
@@ -568,124 +866,21 @@ async fn parse_msg_body<S: StreamExt<Item=tungstenite::Message> + Unpin>(msg_typ
                 msg => panic!("invalid message body {:?}", msg)
             }
         },
-        MessageType::ValueEventTable => {
-            match stream.next().await.unwrap() {
-                tungstenite::Message::Binary(body_msg) => {
-                    let mut pack = Cursor::new(body_msg);
-                    let mut events: Vec<ValueEvent> = Vec::new();
-                    while pack.position() < msg_len {
-                        let uuid = parse_uuid(&mut pack);
-                        let val = pack.read_f64::<LittleEndian>().unwrap();
-                        events.push(ValueEvent(uuid, val));
-                    }
-                    Message::EventTable(EventTable::ValueEvents(events))
-                },
-                msg => panic!("invalid message body {:?}", msg)
-            }
-        },
-        MessageType::TextEventTable => {
-            match stream.next().await.unwrap() {
-                tungstenite::Message::Binary(body_msg) => {
-                    let mut pack = Cursor::new(body_msg);
-                    let mut events: Vec<TextEvent> = Vec::new();
-                    while pack.position() < msg_len {
-                        let uuid = parse_uuid(&mut pack);
-                        let uuid_icon = parse_uuid(&mut pack);
-                        let text_len = pack.read_u32::<LittleEndian>().unwrap().try_into().unwrap();
-                        let mut text_buf = vec![0; text_len];
-                        pack.read_exact(&mut text_buf).unwrap();
-                        let text = String::from_utf8(text_buf).unwrap();
-                        events.push(TextEvent(uuid, uuid_icon, text));
-                        match text_len % 4 {
-                            0 => (),
-                            r => {
-                                pack.seek(SeekFrom::Current((4 - r).try_into().unwrap())).unwrap();
-                            }
-                        }
-                    }
-                    Message::EventTable(EventTable::TextEvents(events))
-                },
-                msg => panic!("invalid message body {:?}", msg)
-            }
-        }
-        MessageType::DaytimerEventTable => {
-            match stream.next().await.unwrap() {
-                tungstenite::Message::Binary(body_msg) => {
-                    let mut pack = Cursor::new(body_msg);
-                    let mut events: Vec<DaytimerEvent> = Vec::new();
-                    while pack.position() < msg_len {
-                        let uuid = parse_uuid(&mut pack);
-                        let default_val = pack.read_f64::<LittleEndian>().unwrap();
-                        let entries_len: usize = pack.read_i32::<LittleEndian>().unwrap().try_into().unwrap();
-                        let mut entries: Vec<LoxoneDaytimerEntry> = Vec::new();
-                        for _ in 0..entries_len {
-                            let mode = pack.read_i32::<LittleEndian>().unwrap();
-                            let from = pack.read_i32::<LittleEndian>().unwrap();
-                            let to = pack.read_i32::<LittleEndian>().unwrap();
-                            let need_activate = pack.read_i32::<LittleEndian>().unwrap();
-                            let value = pack.read_f64::<LittleEndian>().unwrap();
-                            entries.push(LoxoneDaytimerEntry{ mode, from, to, need_activate, value })
-                        }
-                        events.push(DaytimerEvent(uuid, default_val, entries))
-                    }
-                    Message::EventTable(EventTable::DaytimerEvents(events))
-                },
-                msg => panic!("invalid message body {:?}", msg)
-            }
-        },
+        MessageType::ValueEventTable => Message::EventTable(parse_event_table(EventTableKind::Value, msg_len, stream).await),
+        MessageType::TextEventTable => Message::EventTable(parse_event_table(EventTableKind::Text, msg_len, stream).await),
+        MessageType::DaytimerEventTable => Message::EventTable(parse_event_table(EventTableKind::Daytimer, msg_len, stream).await),
         MessageType::OutOfServiceIndicator => Message::OutOfServiceIndicator,
         MessageType::KeepAlive => Message::KeepAlive,
-        MessageType::WeatherEventTable => {
-            match stream.next().await.unwrap() {
-                tungstenite::Message::Binary(body_msg) => {
-                    let mut pack = Cursor::new(body_msg);
-                    let mut events: Vec<WeatherEvent> = Vec::new();
-                    while pack.position() < msg_len {
-                        let uuid = parse_uuid(&mut pack);
-                        let last_update = pack.read_u32::<LittleEndian>().unwrap();
-                        let entries_len: usize = pack.read_i32::<LittleEndian>().unwrap().try_into().unwrap();
-                        let mut entries: Vec<LoxoneWeatherEntry> = Vec::new();
-                        for _ in 0..entries_len {
-                            let timestamp = pack.read_i32::<LittleEndian>().unwrap();
-                            let weather_type = pack.read_i32::<LittleEndian>().unwrap();
-                            let wind_direction = pack.read_i32::<LittleEndian>().unwrap();
-                            let solar_radiation = pack.read_i32::<LittleEndian>().unwrap();
-                            let relative_humidity = pack.read_i32::<LittleEndian>().unwrap();
-                            let temperature = pack.read_f64::<LittleEndian>().unwrap();
-                            let perceived_temperature = pack.read_f64::<LittleEndian>().unwrap();
-                            let dew_point = pack.read_f64::<LittleEndian>().unwrap();
-                            let precipitation = pack.read_f64::<LittleEndian>().unwrap();
-                            let wind_speed = pack.read_f64::<LittleEndian>().unwrap();
-                            let barometic_pressure = pack.read_f64::<LittleEndian>().unwrap();
-                            entries.push(LoxoneWeatherEntry{
-                                timestamp,
-                                weather_type,
-                                wind_direction,
-                                solar_radiation,
-                                relative_humidity,
-                                temperature,
-                                perceived_temperature,
-                                dew_point,
-                                precipitation,
-                                wind_speed,
-                                barometic_pressure
-                            })
-                        }
-                        events.push(WeatherEvent(uuid, last_update, entries))
-                    }
-                    Message::EventTable(EventTable::WeatherEvents(events))
-                },
-                msg => panic!("invalid message body {:?}", msg)
-            }
-        },
+        MessageType::WeatherEventTable => Message::EventTable(parse_event_table(EventTableKind::Weather, msg_len, stream).await),
     }
 }
 
-fn parse_uuid(pack: &mut Cursor<Vec<u8>>) -> LoxoneUUID {
-    let d1 = pack.read_u32::<LittleEndian>().unwrap();
-    let d2 = pack.read_u16::<LittleEndian>().unwrap();
-    let d3 = pack.read_u16::<LittleEndian>().unwrap();
-    let mut d4 = [0; 8];
-    pack.read_exact(&mut d4).unwrap();
-    format!("{:08x}-{:04x}-{:04x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}", d1, d2, d3, d4[0], d4[1], d4[2], d4[3], d4[4], d4[5], d4[6], d4[7])
+async fn parse_event_table<S: StreamExt<Item=tungstenite::Message> + Unpin>(kind: EventTableKind, msg_len: u64, stream: &mut S) -> EventTable {
+    match stream.next().await.unwrap() {
+        tungstenite::Message::Binary(mut body_msg) => {
+            body_msg.truncate(msg_len.try_into().unwrap());
+            EventTable::new(kind, body_msg)
+        },
+        msg => panic!("invalid message body {:?}", msg)
+    }
 }
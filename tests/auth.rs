@@ -0,0 +1,19 @@
+use loxone::errors::RequestError;
+use loxone::hash_visu_pwd;
+
+const KEY: &[u8] = b"ACE78E8DDF3A4014";
+const SALT: &str = "0b2d7a8c9e";
+
+#[test]
+fn visu_password_hash() {
+    assert_eq!(hex::encode(hash_visu_pwd("secret", KEY, SALT, "SHA1").unwrap()), "77d0ae9d11ab228ccbe888eb6d0d934f49f69d87");
+    assert_eq!(hex::encode(hash_visu_pwd("secret", KEY, SALT, "SHA256").unwrap()), "d5c51e258e684b36cc64427153e06163f569254dacfc52490514e79276707fe3");
+}
+
+#[test]
+fn unsupported_hash_alg_is_an_error() {
+    match hash_visu_pwd("secret", KEY, SALT, "MD5") {
+        Err(RequestError::UnsupportedHashAlg(hash_alg)) => assert_eq!(hash_alg, "MD5"),
+        reply => panic!("unexpected reply {:?}", reply),
+    }
+}
@@ -0,0 +1,26 @@
+use loxone::LoxAPP3Cache;
+
+fn cache(name: &str) -> LoxAPP3Cache {
+    LoxAPP3Cache::new(std::env::temp_dir().join(format!("loxone-cache-{}-{}", name, std::process::id())))
+}
+
+#[tokio::test]
+async fn load_matching_version() {
+    let cache = cache("load");
+    assert_eq!(cache.last_modified().await.unwrap(), None);
+    cache.store("2020-10-01 12:34:56", "{}").await.unwrap();
+    assert_eq!(cache.last_modified().await.unwrap().as_deref(), Some("2020-10-01 12:34:56"));
+    assert_eq!(cache.load("2020-10-01 12:34:56").await.unwrap().as_deref(), Some("{}"));
+    cache.clear().await.unwrap();
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
+
+#[tokio::test]
+async fn skip_outdated_version() {
+    let cache = cache("outdated");
+    cache.store("2020-10-01 12:34:56", "{}").await.unwrap();
+    assert_eq!(cache.load("2020-10-02 08:00:00").await.unwrap(), None);
+    cache.clear().await.unwrap();
+    assert_eq!(cache.load("2020-10-01 12:34:56").await.unwrap(), None);
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
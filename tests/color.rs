@@ -0,0 +1,68 @@
+use loxone::loxapp3::color::{LoxoneColor, LoxoneColorSequence};
+use loxone::loxapp3::commands::{ColorPickerCommand, Command};
+use loxone::loxapp3::LoxoneController;
+
+#[test]
+fn parse_and_format_colors() {
+    let color: LoxoneColor = "hsv(120,100,50)".parse().unwrap();
+    assert_eq!(color, LoxoneColor::Hsv { hue: 120, saturation: 100, value: 50 });
+    assert_eq!(color.to_string(), "hsv(120,100,50)");
+
+    let color: LoxoneColor = "temp(50,2700)".parse().unwrap();
+    assert_eq!(color, LoxoneColor::Temp { brightness: 50, kelvin: 2700 });
+    assert_eq!(color.kelvin(), Some(2700));
+    assert_eq!(color.to_string(), "temp(50,2700)");
+
+    assert_eq!("hsv(12.6, 99.5, 0)".parse::<LoxoneColor>().unwrap(), LoxoneColor::Hsv { hue: 13, saturation: 100, value: 0 });
+    assert!("hsv(1,2)".parse::<LoxoneColor>().is_err());
+    assert!("rgb(1,2,3)".parse::<LoxoneColor>().is_err());
+    assert!("temp(50,2700".parse::<LoxoneColor>().is_err());
+}
+
+#[test]
+fn convert_rgb() {
+    assert_eq!(LoxoneColor::from_rgb(255, 0, 0), LoxoneColor::Hsv { hue: 0, saturation: 100, value: 100 });
+    assert_eq!(LoxoneColor::from_rgb(0, 128, 0), LoxoneColor::Hsv { hue: 120, saturation: 100, value: 50 });
+    assert_eq!(LoxoneColor::from_rgb(0, 0, 0), LoxoneColor::Hsv { hue: 0, saturation: 0, value: 0 });
+    assert_eq!(LoxoneColor::Hsv { hue: 240, saturation: 100, value: 100 }.to_rgb(), (0, 0, 255));
+    assert_eq!(LoxoneColor::Hsv { hue: 60, saturation: 50, value: 100 }.to_rgb(), (255, 255, 128));
+
+    let (r, g, b) = LoxoneColor::from_kelvin(100, 2700).to_rgb();
+    assert!(r == 255 && g < 200 && b < 140);
+    assert_eq!(LoxoneColor::from_kelvin(100, 10000).kelvin(), Some(LoxoneColor::MAX_KELVIN));
+    assert_eq!(LoxoneColor::from_kelvin(0, 6500).to_rgb(), (0, 0, 0));
+}
+
+#[test]
+fn parse_favorites_and_sequences() {
+    let favorites = LoxoneColor::parse_favorites(r#"["hsv(0,100,100)","temp(80,4000)"]"#).unwrap();
+    assert_eq!(favorites, [LoxoneColor::Hsv { hue: 0, saturation: 100, value: 100 }, LoxoneColor::Temp { brightness: 80, kelvin: 4000 }]);
+    assert!(LoxoneColor::parse_favorites("").unwrap().is_empty());
+    assert!(LoxoneColor::parse_favorites(r#"["nope"]"#).is_err());
+
+    let sequences = LoxoneColorSequence::parse_favorites(r#"[{"name":"Sunset","colors":["hsv(20,100,100)","hsv(0,100,60)"],"interval":30}]"#).unwrap();
+    assert_eq!(sequences[0].name.as_deref(), Some("Sunset"));
+    assert_eq!(sequences[0].colors.len(), 2);
+    assert_eq!(sequences[0].interval, 30);
+}
+
+#[test]
+fn color_picker_commands() {
+    let picker = LoxoneController::from_value(serde_json::json!({
+        "type": "ColorPickerV2",
+        "states": {
+            "color": "40000000-0000-000a-ffff000000000001",
+            "sequence": "40000000-0000-000a-ffff000000000002",
+            "sequenceColorIdx": "40000000-0000-000a-ffff000000000003",
+        },
+    }));
+    let color = LoxoneColor::Temp { brightness: 40, kelvin: 3000 };
+    assert_eq!(ColorPickerCommand::from(color).mutation(&picker).as_deref(), Some("temp(40,3000)"));
+    assert_eq!(ColorPickerCommand::SetFavorite { index: 2, color }.mutation(&picker).as_deref(), Some("setFav/2/temp(40,3000)"));
+
+    let sequence = LoxoneColorSequence::parse(r#"{"colors":["hsv(0,100,100)"],"interval":5}"#).unwrap();
+    assert_eq!(
+        ColorPickerCommand::PlaySequence(sequence).mutation(&picker).as_deref(),
+        Some("sequence/%7B%22colors%22%3A%5B%22hsv%280%2C100%2C100%29%22%5D%2C%22interval%22%3A5%7D"),
+    );
+}
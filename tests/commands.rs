@@ -0,0 +1,304 @@
+use loxone::loxapp3::commands::{encode_argument, AlarmCommand, AudioZoneCommand, ClimateControllerCommand, Command, CommandTarget, DimmerCommand, GateCommand, IRoomControllerV2Command, JalousieCommand, LightControllerV2Command, SliderCommand, SwitchCommand, TextInputCommand, WindowCommand};
+use loxone::loxapp3::controllers::{AudioZonePlayState, AudioZoneRepeat, ClimateController, ClimateControllerOverwriteReason, ClimateControllerServiceMode, Dimmer, GateAnimation, IRoomControllerV2, IRoomControllerV2Mode, IRoomControllerV2OperatingMode, Jalousie, JalousieAnimation, LightControllerV2, Switch, WindowMonitor, WindowState};
+use loxone::errors::CommandError;
+use loxone::loxapp3::tasks::Task;
+use loxone::loxapp3::{is_valid_uuid, LoxoneApp3, LoxoneController};
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+
+fn states(prefix: &str, names: &[&str]) -> serde_json::Value {
+    names.iter().enumerate().map(|(i, state)| (state.to_string(), format!("{}{:012x}", prefix, i + 1).into())).collect::<serde_json::Map<_, _>>().into()
+}
+
+fn slider() -> LoxoneController {
+    LoxoneController::from_value(serde_json::json!({
+        "type": "Slider",
+        "details": { "format": "%.1f", "min": 0.5, "max": 10.0, "step": 0.5 },
+        "states": { "value": "40000000-0000-0001-ffff000000000001", "error": "40000000-0000-0001-ffff000000000002" },
+    }))
+}
+
+#[test]
+fn commands_match_controller_type() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let dimmer = loxapp3.controls().of_type::<Dimmer>().first().unwrap();
+    let switch = loxapp3.controls().of_type::<Switch>().first().unwrap();
+
+    assert_eq!(DimmerCommand::Set(42.0).mutation(dimmer.controller).as_deref(), Some("42"));
+    assert_eq!(DimmerCommand::Set(120.0).mutation(dimmer.controller).as_deref(), Some("100"));
+    assert_eq!(DimmerCommand::Set(f64::NAN).mutation(dimmer.controller), None);
+    assert_eq!(DimmerCommand::Set(f64::NEG_INFINITY).mutation(dimmer.controller), None);
+    assert_eq!(SwitchCommand::Pulse.mutation(switch.controller).as_deref(), Some("pulse"));
+    assert_eq!(SwitchCommand::On.mutation(dimmer.controller), None);
+    assert_eq!(DimmerCommand::Off.mutation(switch.controller), None);
+}
+
+#[test]
+fn slider_command_is_clamped_and_stepped() {
+    let slider = slider();
+    assert_eq!(SliderCommand::Set(3.7).mutation(&slider).as_deref(), Some("3.5"));
+    assert_eq!(SliderCommand::Set(-2.0).mutation(&slider).as_deref(), Some("0.5"));
+    assert_eq!(SliderCommand::Set(12.0).mutation(&slider).as_deref(), Some("10"));
+    assert_eq!(SliderCommand::Plus.mutation(&slider).as_deref(), Some("plus"));
+}
+
+#[test]
+fn text_arguments_are_percent_encoded() {
+    assert_eq!(encode_argument("Küche / Essen"), "K%C3%BCche%20%2F%20Essen");
+    assert_eq!(encode_argument("a-b_c.d~e"), "a-b_c.d~e");
+    assert_eq!(encode_argument("100%"), "100%25");
+
+    let text_input = LoxoneController::from_value(serde_json::json!({
+        "type": "TextInput",
+        "states": { "text": "40000000-0000-0002-ffff000000000001" },
+    }));
+    assert_eq!(TextInputCommand::Set(String::from("Hello World?")).mutation(&text_input).as_deref(), Some("Hello%20World%3F"));
+    assert_eq!(TextInputCommand::Set(String::from("on/off")).mutation(&text_input).as_deref(), Some("on%2Foff"));
+}
+
+#[test]
+fn mood_name_is_percent_encoded() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let controller = loxapp3.controls().of_type::<LightControllerV2>().first().unwrap();
+    let learn = LightControllerV2Command::Learn { mood_id: 3, mood_name: String::from("Gemütlich/Abend") };
+    assert_eq!(learn.mutation(controller.controller).as_deref(), Some("learn/3/Gem%C3%BCtlich%2FAbend"));
+}
+
+#[test]
+fn uuids_are_validated() {
+    assert!(is_valid_uuid("098802e1-02b4-603c-ffffeee000d80cfd"));
+    assert!(is_valid_uuid("0f86a2fe-0378-3e08-ffff9f3c4ba2f1d5/masterValue"));
+    assert!(!is_valid_uuid("098802e1-02b4-603c-ffffeee000d80cf"));
+    assert!(!is_valid_uuid("098802e1-02b4-603c-ffffeee000d80cfd/"));
+    assert!(!is_valid_uuid("098802e1-02b4-603c-ffffeee000d80cfd/../sys"));
+    assert!(!is_valid_uuid("098802e1-02b4-603c-ffffeee000d80cfg"));
+    assert!(!is_valid_uuid(""));
+}
+
+#[test]
+fn secured_flag_is_exposed_to_command_targets() {
+    let mut loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let uuid = loxapp3.controls().of_type::<Dimmer>().first().unwrap().uuid.clone();
+    assert!(!loxapp3.controls().iter().any(|control| control.is_secured()));
+
+    loxapp3.controls.get_mut(&uuid).unwrap().is_secured = true;
+    let secured: Vec<_> = loxapp3.controls().iter().filter(|control| control.is_secured()).map(|control| control.uuid.clone()).collect();
+    assert_eq!(secured, vec![uuid.clone()]);
+    assert!(loxapp3.walk().filter(|node| node.is_secured()).all(|node| node.uuid == &uuid));
+}
+
+#[test]
+fn secured_controls_cannot_be_scheduled() {
+    let mut loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let uuid = loxapp3.controls().of_type::<Dimmer>().first().unwrap().uuid.clone();
+    let control = &loxapp3.controls[&uuid];
+    assert_eq!(Task::schedule_cmd(control, &DimmerCommand::Set(42.0), 360_201_600).unwrap(), format!("jdev/sps/addcmd/{}/360201600/42", uuid));
+    assert!(matches!(Task::schedule_cmd(control, &SwitchCommand::On, 360_201_600), Err(CommandError::UnsupportedController(_))));
+
+    loxapp3.controls.get_mut(&uuid).unwrap().is_secured = true;
+    let control = &loxapp3.controls[&uuid];
+    match Task::schedule_cmd(control, &DimmerCommand::Set(42.0), 360_201_600) {
+        Err(CommandError::Secured(secured)) => assert_eq!(secured, uuid),
+        reply => panic!("unexpected reply {:?}", reply),
+    }
+}
+
+#[test]
+fn jalousie_commands() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let blinds = loxapp3.controls().of_type::<Jalousie>().first().unwrap();
+    assert_eq!(blinds.data.details.animation, JalousieAnimation::Blinds);
+    assert!(blinds.data.details.is_automatic);
+    assert_eq!(blinds.data.states.shade_position, "30000000-0000-0002-ffff000000000004");
+
+    assert_eq!(JalousieCommand::FullDown.mutation(blinds.controller).as_deref(), Some("FullDown"));
+    assert_eq!(JalousieCommand::ManualPosition(37.5).mutation(blinds.controller).as_deref(), Some("manualPosition/37.5"));
+    assert_eq!(JalousieCommand::ManualLamelle(150.0).mutation(blinds.controller).as_deref(), Some("manualLamelle/100"));
+
+    let central = LoxoneController::from_value(serde_json::json!({
+        "type": "CentralJalousie",
+        "details": { "controls": [{ "uuid": "30000000-0000-0002-ffff000000000000" }] },
+    }));
+    assert_eq!(central.central_controls().unwrap(), ["30000000-0000-0002-ffff000000000000"]);
+    assert_eq!(JalousieCommand::Shade.mutation(&central).as_deref(), Some("shade"));
+    assert_eq!(JalousieCommand::ManualPosition(50.0).mutation(&central), None);
+}
+
+#[test]
+fn gate_and_window_commands() {
+    let gate = LoxoneController::from_value(serde_json::json!({
+        "type": "Gate",
+        "details": { "animation": 0 },
+        "states": {
+            "position": "40000000-0000-0003-ffff000000000001",
+            "active": "40000000-0000-0003-ffff000000000002",
+            "preventOpen": "40000000-0000-0003-ffff000000000003",
+            "preventClose": "40000000-0000-0003-ffff000000000004",
+        },
+    }));
+    match &gate {
+        LoxoneController::Gate(gate) => assert_eq!(gate.details.animation, GateAnimation::GarageDoor),
+        controller => panic!("unexpected controller {:?}", controller),
+    }
+    assert_eq!(GateCommand::PartiallyOpen.mutation(&gate).as_deref(), Some("partiallyOpen"));
+
+    let central = LoxoneController::from_value(serde_json::json!({
+        "type": "CentralGate",
+        "details": { "controls": [{ "uuid": "40000000-0000-0003-ffff000000000000" }] },
+    }));
+    assert_eq!(GateCommand::Close.mutation(&central).as_deref(), Some("close"));
+    assert_eq!(GateCommand::PartiallyOpen.mutation(&central), None);
+
+    let window = LoxoneController::from_value(serde_json::json!({
+        "type": "Window",
+        "states": { "position": "40000000-0000-0004-ffff000000000001", "direction": "40000000-0000-0004-ffff000000000002" },
+    }));
+    assert_eq!(WindowCommand::MoveToPosition(25.0).mutation(&window).as_deref(), Some("moveToPosition/25"));
+    assert_eq!(WindowCommand::Open.mutation(&window).as_deref(), Some("open/on"));
+    assert_eq!(WindowCommand::Open.mutation(&gate), None);
+}
+
+#[test]
+fn window_monitor_states() {
+    let states = WindowMonitor::window_states("1,2,12,0,x");
+    assert_eq!(states.len(), 5);
+    assert!(states[0].is_closed() && !states[0].is_open());
+    assert!(states[1].is_tilted());
+    assert!(states[2].is_open() && states[2].is_locked());
+    assert!(states[3].is_offline());
+    assert_eq!(states[4], WindowState(0));
+}
+
+#[test]
+fn alarm_commands() {
+    let alarm = LoxoneController::from_value(serde_json::json!({
+        "type": "Alarm",
+        "details": { "alert": false, "presenceConnected": true },
+        "states": {
+            "armed": "40000000-0000-0005-ffff000000000001",
+            "nextLevel": "40000000-0000-0005-ffff000000000002",
+            "nextLevelDelay": "40000000-0000-0005-ffff000000000003",
+            "nextLevelDelayTotal": "40000000-0000-0005-ffff000000000004",
+            "level": "40000000-0000-0005-ffff000000000005",
+            "startTime": "40000000-0000-0005-ffff000000000006",
+            "armedDelay": "40000000-0000-0005-ffff000000000007",
+            "armedDelayTotal": "40000000-0000-0005-ffff000000000008",
+            "sensors": "40000000-0000-0005-ffff000000000009",
+            "disabledMove": "40000000-0000-0005-ffff00000000000a",
+        },
+    }));
+    match &alarm {
+        LoxoneController::Alarm(alarm) => assert_eq!(alarm.details.presence_connected, Some(true)),
+        controller => panic!("unexpected controller {:?}", controller),
+    }
+    assert_eq!(AlarmCommand::On { movement: false }.mutation(&alarm).as_deref(), Some("on/0"));
+    assert_eq!(AlarmCommand::DelayedOn { movement: true }.mutation(&alarm).as_deref(), Some("delayedon/1"));
+    assert_eq!(AlarmCommand::DisableMovement(true).mutation(&alarm).as_deref(), Some("dismv/1"));
+    assert_eq!(AlarmCommand::Quit.mutation(&alarm).as_deref(), Some("quit"));
+}
+
+#[test]
+fn audio_zone_commands() {
+    let states = states("40000000-0000-0006-ffff", &[
+        "serverState", "playState", "clientState", "power", "volume", "maxVolume", "volumeStep", "shuffle",
+        "repeat", "songName", "artist", "album", "cover", "duration", "progress",
+    ]);
+    let zone = LoxoneController::from_value(serde_json::json!({
+        "type": "AudioZone",
+        "details": { "server": "40000000-0000-0007-ffff000000000000", "playerid": 3, "clientType": 0 },
+        "states": states,
+    }));
+    match &zone {
+        LoxoneController::AudioZone(zone) => assert_eq!(zone.details.player_id, 3),
+        controller => panic!("unexpected controller {:?}", controller),
+    }
+    assert_eq!(AudioZoneCommand::Volume(120).mutation(&zone).as_deref(), Some("volume/100"));
+    assert_eq!(AudioZoneCommand::Repeat(AudioZoneRepeat::Track).mutation(&zone).as_deref(), Some("repeat/3"));
+    assert_eq!(AudioZoneCommand::Favorite(7).mutation(&zone).as_deref(), Some("source/7"));
+    assert_eq!(AudioZonePlayState::from_value(2.0), AudioZonePlayState::Playing);
+
+    let central = LoxoneController::from_value(serde_json::json!({
+        "type": "CentralAudioZone",
+        "details": { "controls": [{ "uuid": "40000000-0000-0006-ffff000000000000" }] },
+    }));
+    assert_eq!(AudioZoneCommand::Pause.mutation(&central).as_deref(), Some("pause"));
+    assert_eq!(AudioZoneCommand::Next.mutation(&central), None);
+}
+
+#[test]
+fn room_controller_commands() {
+    let controller = LoxoneController::from_value(serde_json::json!({
+        "type": "IRoomControllerV2",
+        "details": {
+            "format": "%.1f°",
+            "timerModes": [
+                { "id": 0, "name": "Eco", "description": "Economy" },
+                { "id": 1, "name": "Comfort", "description": "Comfort temperature" },
+            ],
+            "connectedInputs": 0,
+        },
+        "states": states("40000000-0000-0008-ffff", &[
+            "activeMode", "operatingMode", "overrideEntries", "prepareState", "overrideReason", "tempActual", "tempTarget",
+            "comfortTemperature", "comfortTolerance", "absentMinOffset", "absentMaxOffset", "frostProtectTemperature",
+            "heatProtectTemperature", "comfortTemperatureOffset", "openWindow",
+        ]),
+        "subControls": {},
+    }));
+    let room_controller = match &controller {
+        LoxoneController::IRoomControllerV2(room_controller) => room_controller,
+        controller => panic!("unexpected controller {:?}", controller),
+    };
+
+    let (mode, timer_mode) = room_controller.active_mode(1.0);
+    assert_eq!(mode, IRoomControllerV2Mode::ComfortTemperature);
+    assert_eq!(timer_mode.unwrap().name, "Comfort");
+    assert_eq!(IRoomControllerV2::operating_mode(4.0), IRoomControllerV2OperatingMode::ManualHeating);
+
+    let entries = IRoomControllerV2::override_entries(r#"[{"end":436485600,"isTimer":true,"source":"App","reason":1}]"#).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].is_timer);
+    assert_eq!(entries[0].reason.map(IRoomControllerV2Mode::from), Some(IRoomControllerV2Mode::ComfortTemperature));
+    assert!(IRoomControllerV2::override_entries("").unwrap().is_empty());
+
+    let cmd = IRoomControllerV2Command::StartOverride { mode: IRoomControllerV2Mode::Manual, duration: 3600, temperature: Some(22.5) };
+    assert_eq!(cmd.mutation(&controller).as_deref(), Some("override/3/3600/22.5"));
+    assert_eq!(IRoomControllerV2Command::SetComfortTemperature(21.0).mutation(&controller).as_deref(), Some("setComfortTemperature/21"));
+    assert_eq!(IRoomControllerV2Command::SetComfortTemperature(f64::NAN).mutation(&controller), None);
+    assert_eq!(IRoomControllerV2Command::SetAbsentMaxOffset(f64::INFINITY).mutation(&controller), None);
+    let cmd = IRoomControllerV2Command::StartOverride { mode: IRoomControllerV2Mode::Manual, duration: 3600, temperature: Some(f64::NAN) };
+    assert_eq!(cmd.mutation(&controller), None);
+    assert_eq!(IRoomControllerV2Command::OperatingMode(IRoomControllerV2OperatingMode::Automatic).mutation(&controller).as_deref(), Some("operatingMode/0"));
+}
+
+#[test]
+fn climate_controller_commands() {
+    let controller = LoxoneController::from_value(serde_json::json!({
+        "type": "ClimateController",
+        "details": { "capabilities": 5 },
+        "states": states("40000000-0000-0009-ffff", &[
+            "controls", "currentMode", "autoMode", "currentAutomatic", "temperatureBoundaryInfo", "heatingTempBoundary",
+            "coolingTempBoundary", "actualOutdoorTemp", "averageOutdoorTemp", "overwriteReason", "infoText", "serviceMode",
+            "nextMaintenance", "ventilation",
+        ]),
+    }));
+    match &controller {
+        LoxoneController::ClimateController(climate) => {
+            assert!(climate.details.capabilities.can_heat());
+            assert!(!climate.details.capabilities.can_cool());
+            assert!(climate.details.capabilities.can_ventilate());
+        },
+        controller => panic!("unexpected controller {:?}", controller),
+    }
+
+    let controls = ClimateController::controls(r#"[{"uuid":"30000000-0000-0008-ffff000000000000","demand":1,"temp":20.5}]"#).unwrap();
+    assert_eq!(controls[0].demand, 1);
+    assert_eq!(controls[0].temperature, Some(20.5));
+    assert_eq!(ClimateController::service_mode(1.0), ClimateControllerServiceMode::Standby);
+    assert_eq!(ClimateController::overwrite_reason(1.0), ClimateControllerOverwriteReason::ServiceMode);
+
+    let cmd = ClimateControllerCommand::SetServiceMode(ClimateControllerServiceMode::CoolingOn);
+    assert_eq!(cmd.mutation(&controller).as_deref(), Some("setServiceMode/3"));
+    assert_eq!(ClimateControllerCommand::SetHeatingBoundary(15.5).mutation(&controller).as_deref(), Some("setHeatingBoundary/15.5"));
+    assert_eq!(ClimateControllerCommand::SetHeatingBoundary(f64::NAN).mutation(&controller), None);
+    assert_eq!(ClimateControllerCommand::SetCoolingBoundary(f64::INFINITY).mutation(&controller), None);
+}
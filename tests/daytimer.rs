@@ -0,0 +1,58 @@
+use loxone::loxapp3::commands::{Command, DaytimerCommand};
+use loxone::loxapp3::daytimer::{DaytimerEntry, DaytimerTime, DaytimerValue};
+use loxone::loxapp3::{LoxoneApp3, LoxoneController, LoxoneDaytimerEntry};
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+
+fn daytimer(analog: bool) -> LoxoneController {
+    LoxoneController::from_value(serde_json::json!({
+        "type": "Daytimer",
+        "details": { "analog": analog },
+        "states": {
+            "entriesAndDefaultValue": "40000000-0000-000b-ffff000000000001",
+            "mode": "40000000-0000-000b-ffff000000000002",
+            "value": "40000000-0000-000b-ffff000000000003",
+        },
+    }))
+}
+
+#[test]
+fn daytimer_schedule() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let entries = [
+        LoxoneDaytimerEntry { mode: 1, from: 390, to: 1440, need_activate: 0, value: 21.5 },
+        LoxoneDaytimerEntry { mode: 2, from: 0, to: 1500, need_activate: 1, value: 1.0 },
+    ];
+    let schedule = loxapp3.daytimer_schedule(&entries, true);
+    assert_eq!(schedule.len(), 1);
+    assert_eq!(schedule[0].mode_name.as_deref(), Some("Holiday"));
+    assert_eq!(schedule[0].from.to_string(), "06:30");
+    assert_eq!(schedule[0].to, DaytimerTime::END_OF_DAY);
+    assert_eq!(schedule[0].value, DaytimerValue::Analog(21.5));
+    assert_eq!(schedule[0].to_raw(), entries[0]);
+
+    let schedule = loxapp3.daytimer_schedule(&entries[..1], false);
+    assert_eq!(schedule[0].value, DaytimerValue::Digital(true));
+}
+
+#[test]
+fn daytimer_commands() {
+    let entry = DaytimerEntry {
+        mode: 0,
+        mode_name: None,
+        from: DaytimerTime { hour: 7, minute: 15 },
+        to: DaytimerTime { hour: 22, minute: 0 },
+        need_activate: true,
+        value: DaytimerValue::Analog(20.0),
+    };
+    let cmd = DaytimerCommand::Set(vec![entry.clone(), entry]);
+    assert_eq!(cmd.mutation(&daytimer(true)).as_deref(), Some("set/2/0;435;1320;1;20/0;435;1320;1;20"));
+    assert_eq!(cmd.mutation(&daytimer(false)).as_deref(), Some("set/2/0;435;1320;1/0;435;1320;1"));
+    assert_eq!(DaytimerCommand::StartOverride { value: 18.0, duration: 600 }.mutation(&daytimer(true)).as_deref(), Some("startOverride/18/600"));
+    assert_eq!(DaytimerCommand::Default(0.0).mutation(&daytimer(false)).as_deref(), Some("default/0"));
+    assert_eq!(DaytimerCommand::Pulse.mutation(&daytimer(false)).as_deref(), Some("pulse"));
+
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let dimmer = &loxapp3.controls["30000000-0000-0001-ffff000000000000"].controller;
+    assert_eq!(DaytimerCommand::Pulse.mutation(dimmer), None);
+}
@@ -0,0 +1,26 @@
+use loxone::diagnostics::{self, FirmwareVersion, Heap};
+
+#[test]
+fn parse_firmware_version() {
+    let version: FirmwareVersion = "12.0.2.24".parse().unwrap();
+    assert_eq!(version, FirmwareVersion { major: 12, minor: 0, patch: 2, build: 24 });
+    assert_eq!(version.to_string(), "12.0.2.24");
+    assert!(version > "11.3.2.11".parse().unwrap());
+    assert_eq!("10.2".parse::<FirmwareVersion>().unwrap().to_string(), "10.2.0.0");
+    assert!("v12".parse::<FirmwareVersion>().is_err());
+}
+
+#[test]
+fn parse_values() {
+    assert_eq!(diagnostics::parse_counter("42"), Some(42));
+    assert_eq!(diagnostics::parse_counter(" 1024kB"), Some(1024));
+    assert_eq!(diagnostics::parse_counter("n/a"), None);
+    assert_eq!(diagnostics::parse_percent("13%"), Some(13.0));
+    assert_eq!(diagnostics::parse_percent("7.5"), Some(7.5));
+    assert_eq!(diagnostics::parse_flag("1"), Some(true));
+    assert_eq!(diagnostics::parse_flag("off"), Some(false));
+    assert_eq!(diagnostics::parse_flag("maybe"), None);
+    assert_eq!(Heap::parse("12345/65000kB"), Some(Heap { used: 12345, total: Some(65000) }));
+    assert_eq!(Heap::parse("12345kB"), Some(Heap { used: 12345, total: None }));
+    assert_eq!(Heap::parse("12345/"), None);
+}
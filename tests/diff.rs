@@ -0,0 +1,107 @@
+use loxone::loxapp3::diff::LoxoneApp3Diff;
+use loxone::loxapp3::index::LoxoneStateIndex;
+use loxone::loxapp3::LoxoneApp3;
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+
+fn loxapp3() -> LoxoneApp3 {
+    serde_json::from_str(LOXAPP3).unwrap()
+}
+
+#[test]
+fn diff_identical_structure() {
+    assert!(LoxoneApp3Diff::new(&loxapp3(), &loxapp3()).is_empty());
+}
+
+#[test]
+fn diff_added_removed_renamed_controls() {
+    let old = loxapp3();
+    let mut new = loxapp3();
+    let mut control = new.controls.remove("30000000-0000-0002-ffff000000000000").unwrap();
+    control.uuid_action = "30000000-0000-0008-ffff000000000000".to_owned();
+    new.controls.insert(control.uuid_action.clone(), control);
+    new.controls.get_mut("30000000-0000-0001-ffff000000000000").unwrap().name = "Pendant Light".to_owned();
+
+    let diff = LoxoneApp3Diff::new(&old, &new);
+    assert_eq!(diff.added_controls.iter().map(|control| control.uuid.as_str()).collect::<Vec<_>>(), vec!["30000000-0000-0008-ffff000000000000"]);
+    assert_eq!(diff.removed_controls.iter().map(|control| control.uuid.as_str()).collect::<Vec<_>>(), vec!["30000000-0000-0002-ffff000000000000"]);
+    assert_eq!(diff.renamed_controls.len(), 1);
+    assert_eq!(diff.renamed_controls[0].old_name, "Ceiling Light");
+    assert_eq!(diff.renamed_controls[0].new_name, "Pendant Light");
+}
+
+#[test]
+fn diff_sub_controls() {
+    let old = loxapp3();
+    let mut new = loxapp3();
+    let control = new.controls.get_mut("30000000-0000-0005-ffff000000000000").unwrap();
+    match &mut control.controller {
+        loxone::loxapp3::LoxoneController::LightControllerV2(controller) => controller.sub_controls.remove("30000000-0000-0007-ffff000000000000"),
+        controller => panic!("unexpected controller {:?}", controller),
+    };
+    let diff = LoxoneApp3Diff::new(&old, &new);
+    assert_eq!(diff.removed_controls.len(), 1);
+    assert_eq!(diff.removed_controls[0].uuid, "30000000-0000-0007-ffff000000000000");
+    assert_eq!(diff.removed_controls[0].name, "Spots");
+}
+
+#[test]
+fn diff_rooms_and_categories() {
+    let old = loxapp3();
+    let mut new = loxapp3();
+    new.rooms.remove("10000000-0000-0001-ffff000000000000");
+    let mut cat = new.cats.remove("20000000-0000-0002-ffff000000000000").unwrap();
+    cat.uuid = "20000000-0000-0003-ffff000000000000".to_owned();
+    cat.name = "Blinds".to_owned();
+    new.cats.insert(cat.uuid.clone(), cat);
+
+    let diff = LoxoneApp3Diff::new(&old, &new);
+    assert!(diff.added_rooms.is_empty());
+    assert_eq!(diff.removed_rooms[0].name, "Kitchen");
+    assert_eq!(diff.added_categories[0].name, "Blinds");
+    assert_eq!(diff.removed_categories[0].name, "Shading");
+}
+
+#[test]
+fn diff_moved_controls_states_and_types() {
+    let old = loxapp3();
+    let mut new: serde_json::Value = serde_json::from_str(LOXAPP3).unwrap();
+    let control = &mut new["controls"]["30000000-0000-0001-ffff000000000000"];
+    control["room"] = "10000000-0000-0002-ffff000000000000".into();
+    control["type"] = "Switch".into();
+    control["states"] = serde_json::json!({ "active": "30000000-0000-0001-ffff000000000001" });
+    let new: LoxoneApp3 = serde_json::from_value(new).unwrap();
+
+    let diff = LoxoneApp3Diff::new(&old, &new);
+    assert_eq!(diff.moved_controls.len(), 1);
+    assert_eq!(diff.moved_controls[0].old_room.as_deref(), Some("10000000-0000-0001-ffff000000000000"));
+    assert_eq!(diff.moved_controls[0].new_room.as_deref(), Some("10000000-0000-0002-ffff000000000000"));
+    assert_eq!(diff.moved_controls[0].old_cat, diff.moved_controls[0].new_cat);
+    assert_eq!(diff.changed_types[0].old_type, "Dimmer");
+    assert_eq!(diff.changed_types[0].new_type, "Switch");
+    let states: Vec<_> = diff.changed_states.iter().map(|change| (change.state.as_str(), change.old_uuid.is_some(), change.new_uuid.is_some())).collect();
+    assert!(states.contains(&("position", true, false)));
+    assert!(states.contains(&("active", false, true)));
+}
+
+#[test]
+fn diff_report() {
+    let old = loxapp3();
+    let mut new = loxapp3();
+    new.controls.get_mut("30000000-0000-0001-ffff000000000000").unwrap().name = "Pendant Light".to_owned();
+
+    let diff = LoxoneApp3Diff::new(&old, &new);
+    assert_eq!(diff.to_string(), "~ control \"Ceiling Light\" renamed to \"Pendant Light\" (30000000-0000-0001-ffff000000000000)\n");
+    let report = serde_json::to_value(&diff).unwrap();
+    assert_eq!(report["renamed_controls"][0]["new_name"], "Pendant Light");
+    assert_eq!(LoxoneApp3Diff::new(&old, &old).to_string(), "no changes\n");
+}
+
+#[test]
+fn index_resolves_states() {
+    let index = LoxoneStateIndex::new(&loxapp3());
+    let state = index.get("30000000-0000-0006-ffff000000000001").unwrap();
+    assert_eq!(state.control, "30000000-0000-0006-ffff000000000000");
+    assert_eq!(state.state, "position");
+    assert_eq!(index.get("30000000-0000-0002-ffff000000000004").unwrap().state, "shadePosition");
+}
@@ -0,0 +1,182 @@
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use std::collections::HashMap;
+
+use loxone::events::{EventTable, EventTableKind, TextEvents, ValueEvents};
+use loxone::loxapp3::{LoxoneDaytimerEntry, LoxoneState, LoxoneUUID, LoxoneWeatherEntry};
+
+const LIGHT: &str = "30000000-0000-0001-ffff000000000001";
+const BLINDS: &str = "30000000-0000-0002-ffff0000000000ab";
+const ICON: &str = "00000000-0000-0020-2000000000000000";
+
+/// Writes the given textual UUID in its binary representation.
+fn write_uuid(buf: &mut Vec<u8>, uuid: &str) {
+    let groups: Vec<_> = uuid.split('-').collect();
+    buf.write_u32::<LittleEndian>(u32::from_str_radix(groups[0], 16).unwrap()).unwrap();
+    buf.write_u16::<LittleEndian>(u16::from_str_radix(groups[1], 16).unwrap()).unwrap();
+    buf.write_u16::<LittleEndian>(u16::from_str_radix(groups[2], 16).unwrap()).unwrap();
+    buf.extend_from_slice(&hex::decode(groups[3]).unwrap());
+}
+
+fn write_text(buf: &mut Vec<u8>, uuid: &str, uuid_icon: &str, text: &str) {
+    write_uuid(buf, uuid);
+    write_uuid(buf, uuid_icon);
+    buf.write_u32::<LittleEndian>(text.len() as u32).unwrap();
+    buf.extend_from_slice(text.as_bytes());
+    buf.resize(buf.len() + (4 - text.len() % 4) % 4, 0);
+}
+
+/// Decodes the given table both borrowed and owned, checking that they agree.
+fn states(kind: EventTableKind, body: Vec<u8>) -> HashMap<LoxoneUUID, LoxoneState> {
+    let table = EventTable::new(kind, body);
+    let states = HashMap::from(&table);
+    let (lower, upper) = table.states().size_hint();
+    assert!(lower <= states.len() && upper.is_some_and(|upper| states.len() <= upper));
+    assert_eq!(table.into_iter().collect::<HashMap<_, _>>(), states);
+    states
+}
+
+fn daytimer_entry(mode: i32, from: i32, to: i32, value: f64) -> LoxoneDaytimerEntry {
+    LoxoneDaytimerEntry { mode, from, to, need_activate: 0, value }
+}
+
+fn weather_entry(timestamp: i32, temperature: f64) -> LoxoneWeatherEntry {
+    LoxoneWeatherEntry {
+        timestamp,
+        weather_type: 7,
+        wind_direction: 270,
+        solar_radiation: 350,
+        relative_humidity: 65,
+        temperature,
+        perceived_temperature: temperature - 1.5,
+        dew_point: 9.25,
+        precipitation: 0.5,
+        wind_speed: 12.0,
+        barometric_pressure: 1013.0,
+    }
+}
+
+#[test]
+fn value_table() {
+    let mut buf = Vec::new();
+    write_uuid(&mut buf, LIGHT);
+    buf.write_f64::<LittleEndian>(42.5).unwrap();
+    write_uuid(&mut buf, BLINDS);
+    buf.write_f64::<LittleEndian>(-1.0).unwrap();
+
+    assert_eq!(ValueEvents::new(&buf).len(), 2);
+    let expected: HashMap<_, _> = vec![
+        (LIGHT.to_owned(), LoxoneState::Value(42.5)),
+        (BLINDS.to_owned(), LoxoneState::Value(-1.0)),
+    ].into_iter().collect();
+    assert_eq!(states(EventTableKind::Value, buf), expected);
+}
+
+#[test]
+fn text_table() {
+    let mut buf = Vec::new();
+    write_text(&mut buf, LIGHT, ICON, "Küche");
+    write_text(&mut buf, BLINDS, ICON, "Open");
+    write_text(&mut buf, ICON, LIGHT, "");
+
+    let texts: Vec<_> = TextEvents::new(&buf).map(|event| event.text.into_owned()).collect();
+    assert_eq!(texts, ["Küche", "Open", ""]);
+    let expected: HashMap<_, _> = vec![
+        (LIGHT.to_owned(), LoxoneState::Text("Küche".to_owned(), ICON.to_owned())),
+        (BLINDS.to_owned(), LoxoneState::Text("Open".to_owned(), ICON.to_owned())),
+        (ICON.to_owned(), LoxoneState::Text(String::new(), LIGHT.to_owned())),
+    ].into_iter().collect();
+    assert_eq!(states(EventTableKind::Text, buf), expected);
+}
+
+#[test]
+fn daytimer_table() {
+    let entries = vec![daytimer_entry(0, 0, 360, 18.0), daytimer_entry(1, 360, 1320, 21.5)];
+    let mut buf = Vec::new();
+    write_uuid(&mut buf, LIGHT);
+    buf.write_f64::<LittleEndian>(20.0).unwrap();
+    buf.write_i32::<LittleEndian>(entries.len() as i32).unwrap();
+    for entry in &entries {
+        buf.write_i32::<LittleEndian>(entry.mode).unwrap();
+        buf.write_i32::<LittleEndian>(entry.from).unwrap();
+        buf.write_i32::<LittleEndian>(entry.to).unwrap();
+        buf.write_i32::<LittleEndian>(entry.need_activate).unwrap();
+        buf.write_f64::<LittleEndian>(entry.value).unwrap();
+    }
+    write_uuid(&mut buf, BLINDS);
+    buf.write_f64::<LittleEndian>(0.0).unwrap();
+    buf.write_i32::<LittleEndian>(0).unwrap();
+
+    let expected: HashMap<_, _> = vec![
+        (LIGHT.to_owned(), LoxoneState::Daytimer(entries, 20.0)),
+        (BLINDS.to_owned(), LoxoneState::Daytimer(Vec::new(), 0.0)),
+    ].into_iter().collect();
+    assert_eq!(states(EventTableKind::Daytimer, buf), expected);
+}
+
+#[test]
+fn weather_table() {
+    let entries = vec![weather_entry(360_201_600, 18.5), weather_entry(360_205_200, 19.75)];
+    let mut buf = Vec::new();
+    write_uuid(&mut buf, LIGHT);
+    buf.write_u32::<LittleEndian>(360_200_000).unwrap();
+    buf.write_i32::<LittleEndian>(entries.len() as i32).unwrap();
+    for entry in &entries {
+        for val in &[entry.timestamp, entry.weather_type, entry.wind_direction, entry.solar_radiation, entry.relative_humidity] {
+            buf.write_i32::<LittleEndian>(*val).unwrap();
+        }
+        for val in &[entry.temperature, entry.perceived_temperature, entry.dew_point, entry.precipitation, entry.wind_speed, entry.barometric_pressure] {
+            buf.write_f64::<LittleEndian>(*val).unwrap();
+        }
+    }
+
+    let expected: HashMap<_, _> = vec![(LIGHT.to_owned(), LoxoneState::Weather(entries, 360_200_000))].into_iter().collect();
+    assert_eq!(states(EventTableKind::Weather, buf), expected);
+}
+
+#[test]
+fn truncated_tables_keep_complete_events() {
+    let mut buf = Vec::new();
+    write_uuid(&mut buf, LIGHT);
+    buf.write_f64::<LittleEndian>(1.0).unwrap();
+    write_uuid(&mut buf, BLINDS);
+    buf.write_f32::<LittleEndian>(2.0).unwrap();
+    let expected: HashMap<_, _> = vec![(LIGHT.to_owned(), LoxoneState::Value(1.0))].into_iter().collect();
+    assert_eq!(states(EventTableKind::Value, buf), expected);
+
+    let mut buf = Vec::new();
+    write_text(&mut buf, LIGHT, ICON, "Open");
+    write_text(&mut buf, BLINDS, ICON, "Closed");
+    buf.truncate(buf.len() - 8);
+    let expected: HashMap<_, _> = vec![(LIGHT.to_owned(), LoxoneState::Text("Open".to_owned(), ICON.to_owned()))].into_iter().collect();
+    assert_eq!(states(EventTableKind::Text, buf), expected);
+
+    let mut buf = Vec::new();
+    write_uuid(&mut buf, LIGHT);
+    buf.write_f64::<LittleEndian>(20.0).unwrap();
+    buf.write_i32::<LittleEndian>(3).unwrap();
+    buf.extend_from_slice(&[0; 24]);
+    assert!(states(EventTableKind::Daytimer, buf).is_empty());
+
+    let mut buf = Vec::new();
+    write_uuid(&mut buf, LIGHT);
+    buf.write_u32::<LittleEndian>(360_200_000).unwrap();
+    buf.write_i32::<LittleEndian>(1).unwrap();
+    buf.extend_from_slice(&[0; 40]);
+    assert!(states(EventTableKind::Weather, buf).is_empty());
+    assert!(states(EventTableKind::Weather, Vec::new()).is_empty());
+}
+
+#[test]
+fn owned_tables_are_decoded_lazily() {
+    let mut buf = Vec::new();
+    write_text(&mut buf, LIGHT, ICON, "Open");
+    write_text(&mut buf, BLINDS, ICON, "Closed");
+    let mut states = EventTable::new(EventTableKind::Text, buf).into_iter();
+    assert_eq!(states.size_hint(), (0, Some(2)));
+    assert_eq!(states.next(), Some((LIGHT.to_owned(), LoxoneState::Text("Open".to_owned(), ICON.to_owned()))));
+    assert_eq!(states.size_hint(), (0, Some(1)));
+    assert_eq!(states.next(), Some((BLINDS.to_owned(), LoxoneState::Text("Closed".to_owned(), ICON.to_owned()))));
+    assert_eq!(states.next(), None);
+    assert_eq!(states.size_hint(), (0, Some(0)));
+}
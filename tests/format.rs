@@ -0,0 +1,62 @@
+use loxone::loxapp3::format::format_value;
+use loxone::loxapp3::LoxoneController;
+
+#[test]
+fn format_c_style() {
+    assert_eq!(format_value("%.1f°C", 21.46), "21.5°C");
+    assert_eq!(format_value("%d%%", 42.6), "43%");
+    assert_eq!(format_value("%.0f", -0.4), "0");
+    assert_eq!(format_value("%.2f kW", -1.5), "-1.50 kW");
+    assert_eq!(format_value("%05.1f", 3.25), "003.2");
+    assert_eq!(format_value("%+d", 5.0), "+5");
+    assert_eq!(format_value("[%-4d]", 7.0), "[7   ]");
+    assert_eq!(format_value("%4d", -7.0), "  -7");
+    assert_eq!(format_value("%s", 2.5), "2.5");
+    assert_eq!(format_value("%lu Wh", 12.0), "12 Wh");
+    assert_eq!(format_value("%x", 255.0), "ff");
+    assert_eq!(format_value("%q and %", 1.0), "%q and %");
+    assert_eq!(format_value("no value", 1.0), "no value");
+}
+
+#[test]
+fn format_placeholders() {
+    assert_eq!(format_value("<v.u>", 93_784.0), "1d 2h 3m 4s");
+    assert_eq!(format_value("<v.u>", 3600.0), "1h");
+    assert_eq!(format_value("<v.u>", 0.0), "0s");
+    assert_eq!(format_value("<v.u>", -90.0), "-1m 30s");
+    assert_eq!(format_value("<v.t>", 360_201_600.0 + 45_000.0), "2020-06-01 12:30");
+    assert_eq!(format_value("<v.x>", 4095.0), "FFF");
+    assert_eq!(format_value("<v.2> m³", 1.0), "1.00 m³");
+    assert_eq!(format_value("<v> kWh", 1.25), "1.25 kWh");
+    assert_eq!(format_value("<v.q> <b>", 1.0), "<v.q> <b>");
+}
+
+#[test]
+fn display_info_only() {
+    let analog = LoxoneController::from_value(serde_json::json!({
+        "type": "InfoOnlyAnalog",
+        "details": { "format": "%.1f°" },
+        "states": { "value": "50000000-0000-0002-ffff000000000001" },
+    }));
+    match analog {
+        LoxoneController::InfoOnlyAnalog(analog) => assert_eq!(analog.details.display(20.04), "20.0°"),
+        controller => panic!("unexpected controller {:?}", controller),
+    }
+
+    let digital = LoxoneController::from_value(serde_json::json!({
+        "type": "InfoOnlyDigital",
+        "details": {
+            "text": { "on": "Open", "off": "Closed" },
+            "image": { "on": "00000000-0000-0001-2000000000000000", "off": "00000000-0000-0002-2000000000000000" },
+            "color": { "on": "#E73246", "off": "#69C350" },
+        },
+        "states": { "value": "50000000-0000-0003-ffff000000000001" },
+    }));
+    match digital {
+        LoxoneController::InfoOnlyDigital(digital) => {
+            assert_eq!(digital.details.display(1.0), ("Open", "#E73246"));
+            assert_eq!(digital.details.display(0.0), ("Closed", "#69C350"));
+        },
+        controller => panic!("unexpected controller {:?}", controller),
+    }
+}
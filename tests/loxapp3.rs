@@ -0,0 +1,219 @@
+use loxone::loxapp3::{LoxoneApp3, LoxoneController, LoxoneRestrictions, LoxoneStatisticFrequency, LoxoneSubControl};
+
+use serde_json::Value;
+
+use std::collections::BTreeMap;
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+
+/// Converts all numbers to floats, integers may come back as floats for fields modelled as `f64`.
+fn normalize(val: Value) -> Value {
+    match val {
+        Value::Number(num) => serde_json::json!(num.as_f64().unwrap()),
+        Value::Array(vals) => Value::Array(vals.into_iter().map(normalize).collect()),
+        Value::Object(fields) => Value::Object(fields.into_iter().map(|(key, val)| (key, normalize(val))).collect()),
+        val => val,
+    }
+}
+
+#[test]
+fn round_trip_structure_file() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let original: Value = serde_json::from_str(LOXAPP3).unwrap();
+    assert_eq!(normalize(serde_json::to_value(&loxapp3).unwrap()), normalize(original));
+}
+
+#[test]
+fn round_trip_is_stable() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let json = serde_json::to_value(&loxapp3).unwrap();
+    let reparsed: LoxoneApp3 = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&reparsed).unwrap(), json);
+}
+
+#[test]
+fn round_trip_keeps_type_tag() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let control = serde_json::to_value(&loxapp3.controls["30000000-0000-0001-ffff000000000000"]).unwrap();
+    assert_eq!(control["type"], "Dimmer");
+    assert_eq!(control["states"]["position"], "30000000-0000-0001-ffff000000000001");
+}
+
+#[test]
+fn round_trip_keeps_unknown_fields() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    match &loxapp3.controls["30000000-0000-0001-ffff000000000000"].controller {
+        LoxoneController::Dimmer(dimmer) => {
+            assert!(!dimmer.extra.contains_key("type"));
+            assert_eq!(dimmer.extra["preset"]["name"], "Dimmer");
+            assert_eq!(dimmer.states.extra["futureState"], "30000000-0000-0001-ffff000000000005");
+        },
+        controller => panic!("unexpected controller {:?}", controller),
+    }
+    let control = serde_json::to_value(&loxapp3.controls["30000000-0000-0001-ffff000000000000"]).unwrap();
+    assert_eq!(control["details"]["format"], "%.0f%%");
+    assert_eq!(control["states"]["futureState"], "30000000-0000-0001-ffff000000000005");
+}
+
+#[test]
+fn round_trip_keeps_unknown_controllers() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let control = &loxapp3.controls["30000000-0000-0003-ffff000000000000"];
+    assert_eq!(control.controller.type_name(), "PresenceDetector");
+    let control = serde_json::to_value(control).unwrap();
+    assert_eq!(control["type"], "PresenceDetector");
+    assert_eq!(control["states"]["active"], "30000000-0000-0003-ffff000000000001");
+}
+
+#[test]
+fn warnings_report_malformed_and_unknown_controllers() {
+    let mut json: Value = serde_json::from_str(LOXAPP3).unwrap();
+    json["controls"]["30000000-0000-0001-ffff000000000000"].as_object_mut().unwrap().remove("states");
+    let loxapp3: LoxoneApp3 = serde_json::from_value(json).unwrap();
+
+    let mut warnings = loxapp3.warnings();
+    warnings.sort_by(|a, b| a.uuid.cmp(&b.uuid));
+    assert_eq!(warnings.len(), 3, "{:?}", warnings);
+    assert_eq!(warnings[0].uuid, "30000000-0000-0001-ffff000000000000");
+    assert_eq!(warnings[0].type_name, "Dimmer");
+    assert!(warnings[0].message.starts_with("malformed Dimmer controller: missing field `states`"), "{}", warnings[0].message);
+    assert_eq!(warnings[1].uuid, "30000000-0000-0003-ffff000000000000");
+    assert_eq!(warnings[1].type_name, "PresenceDetector");
+    assert_eq!(warnings[1].message, "unsupported controller type PresenceDetector");
+    assert_eq!(warnings[2].uuid, "30000000-0000-0004-ffff000000000000");
+    assert_eq!(warnings[2].type_name, "Switch");
+}
+
+#[test]
+fn typed_control_fields() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let control = &loxapp3.controls["30000000-0000-0001-ffff000000000000"];
+    assert_eq!(control.restrictions(), LoxoneRestrictions(LoxoneRestrictions::INTERNAL_ONLY));
+    assert!(control.restrictions().is_internal_only());
+    assert!(!control.restrictions().is_read_only());
+    let statistic = control.statistic.as_ref().unwrap();
+    assert_eq!(statistic.frequency, LoxoneStatisticFrequency::EveryChange);
+    assert_eq!(statistic.outputs.len(), 1);
+    assert_eq!(statistic.outputs[0].id, 0);
+    assert_eq!(statistic.outputs[0].name, "Position");
+    assert_eq!(statistic.outputs[0].format, "%.0f%%");
+    assert_eq!(statistic.outputs[0].uuid, "30000000-0000-0001-ffff000000000001");
+    assert_eq!(statistic.outputs[0].visu_type, 0);
+
+    let control = &loxapp3.controls["30000000-0000-0002-ffff000000000000"];
+    assert_eq!(control.restrictions, Some(LoxoneRestrictions(0)));
+    assert!(control.restrictions().is_empty());
+    assert_eq!(control.has_control_notes, Some(false));
+    assert_eq!(control.secured_details, Some(false));
+    assert_eq!(control.default_icon, Some(None));
+    assert_eq!(control.default_icon(), None);
+    assert_eq!(control.cat().map(String::as_str), Some("20000000-0000-0002-ffff000000000000"));
+    assert!(control.statistic.is_none());
+
+    let control = &loxapp3.controls["30000000-0000-0003-ffff000000000000"];
+    assert_eq!(control.cat, Some(None));
+    assert_eq!(control.restrictions, None);
+    assert!(!control.has_control_notes());
+}
+
+#[test]
+fn typed_sub_control_fields() {
+    let sub_control: LoxoneSubControl = serde_json::from_value(serde_json::json!({
+        "name": "Floor Lamp",
+        "type": "Switch",
+        "uuidAction": "30000000-0000-0006-ffff000000000000",
+        "defaultRating": 0,
+        "isFavorite": false,
+        "isSecured": true,
+        "restrictions": 3,
+        "states": { "active": "30000000-0000-0006-ffff000000000001" },
+        "statistic": {
+            "frequency": 6,
+            "outputs": [{ "id": 1, "name": "Active", "format": "%d", "uuid": "30000000-0000-0006-ffff000000000001", "visuType": 1 }],
+        },
+    })).unwrap();
+    assert!(sub_control.is_secured);
+    assert!(sub_control.restrictions().is_internal_only());
+    assert!(sub_control.restrictions().is_read_only());
+    assert_eq!(sub_control.restrictions().bits(), 3);
+    let statistic = sub_control.statistic.as_ref().unwrap();
+    assert_eq!(statistic.frequency, LoxoneStatisticFrequency::Hour);
+    assert_eq!(statistic.outputs.len(), 1);
+    assert_eq!(statistic.outputs[0].name, "Active");
+    assert_eq!(statistic.outputs[0].visu_type, 1);
+
+    let sub_control: LoxoneSubControl = serde_json::from_value(serde_json::json!({
+        "name": "Spots",
+        "type": "Switch",
+        "uuidAction": "30000000-0000-0007-ffff000000000000",
+        "defaultRating": 0,
+        "isFavorite": false,
+        "isSecured": false,
+        "states": { "active": "30000000-0000-0007-ffff000000000001" },
+        "statistic": { "frequency": 42 },
+    })).unwrap();
+    assert!(sub_control.restrictions().is_empty());
+    let statistic = sub_control.statistic.as_ref().unwrap();
+    assert_eq!(statistic.frequency, LoxoneStatisticFrequency::Unknown(42));
+    assert!(statistic.outputs.is_empty());
+}
+
+#[test]
+fn typed_autopilot_and_weather_server() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let autopilot = loxapp3.autopilot.as_ref().unwrap();
+    assert_eq!(autopilot.name, "Automatic Rules");
+    assert_eq!(autopilot.uuid_action, "40000000-0000-0001-ffff000000000000");
+    assert_eq!(autopilot.states.changed, "40000000-0000-0001-ffff000000000001");
+    assert_eq!(autopilot.states.history, "40000000-0000-0001-ffff000000000002");
+
+    let weather_server = loxapp3.weather_server.as_ref().unwrap();
+    assert_eq!(weather_server.states.actual, "50000000-0000-0001-ffff000000000001");
+    assert_eq!(weather_server.states.forecast, "50000000-0000-0001-ffff000000000002");
+    assert_eq!(weather_server.format.len(), 6);
+    assert_eq!(weather_server.format["temperature"], "%.1f°");
+    assert_eq!(weather_server.format["relativeHumidity"], "%.0f%%");
+    assert_eq!(weather_server.format["barometricPressure"], "%.0fhPa");
+    assert_eq!(weather_server.weather_type_texts[&16], "Heavy rain");
+    assert!(weather_server.weather_field_types[&0].analog);
+}
+
+#[test]
+fn controller_states_by_name() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let states = loxapp3.controls["30000000-0000-0001-ffff000000000000"].controller.states();
+    assert_eq!(states.len(), 5);
+    assert_eq!(states["position"], "30000000-0000-0001-ffff000000000001");
+    assert_eq!(states["futureState"], "30000000-0000-0001-ffff000000000005");
+
+    let states = loxapp3.controls["30000000-0000-0003-ffff000000000000"].controller.states();
+    assert_eq!(states.into_iter().collect::<Vec<_>>(), vec![("active".to_owned(), "30000000-0000-0003-ffff000000000001".to_owned())]);
+    assert!(loxapp3.controls["30000000-0000-0004-ffff000000000000"].controller.states().is_empty());
+
+    let central = LoxoneController::from_value(serde_json::json!({
+        "type": "CentralJalousie",
+        "details": { "controls": [{ "uuid": "30000000-0000-0002-ffff000000000000" }] },
+    }));
+    assert!(central.states().is_empty());
+    assert!(serde_json::to_value(&central).unwrap().get("states").is_none());
+    let central = LoxoneController::from_value(serde_json::json!({
+        "type": "CentralJalousie",
+        "details": { "controls": [{ "uuid": "30000000-0000-0002-ffff000000000000" }] },
+        "states": { "autoActive": "30000000-0000-0008-ffff000000000001", "count": 2 },
+    }));
+    assert_eq!(central.states().into_iter().collect::<Vec<_>>(), vec![("autoActive".to_owned(), "30000000-0000-0008-ffff000000000001".to_owned())]);
+}
+
+#[test]
+fn controller_states_match_structure_file() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let original: Value = serde_json::from_str(LOXAPP3).unwrap();
+    for node in loxapp3.walk() {
+        let control = match node.path.split_first() {
+            Some((parent, _)) => &original["controls"][parent.as_str()]["subControls"][node.uuid.as_str()],
+            None => &original["controls"][node.uuid.as_str()],
+        };
+        let expected: BTreeMap<_, _> = control["states"].as_object().into_iter().flatten().map(|(state, uuid)| (state.to_owned(), uuid.as_str().unwrap().to_owned())).collect();
+        assert_eq!(node.controller.states(), expected, "{}", node.name);
+    }
+}
@@ -0,0 +1,66 @@
+use chrono::{FixedOffset, TimeZone};
+use loxone::loxapp3::notifications::{MessageCenter, MessageCenterCommand, MessageSeverity, Notification, NotificationLevel, NotificationType};
+use loxone::loxapp3::LoxoneApp3;
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+
+#[test]
+fn parse_notifications() {
+    let text = r#"{"uid":"1a2b","ts":360246600,"type":10,"title":"Front door","message":"Door is open","data":{"lvl":2,"uuid":"30000000-0000-0001-ffff000000000000"}}"#;
+    let notifications = Notification::parse(text).unwrap();
+    assert_eq!(notifications.len(), 1);
+    let notification = &notifications[0];
+    assert_eq!(notification.r#type, NotificationType::Normal);
+    assert_eq!(notification.level(), Some(NotificationLevel::Error));
+    assert_eq!(notification.title, "Front door");
+    let cest = FixedOffset::east(2 * 3600);
+    assert_eq!(notification.time(&cest), Some(cest.ymd(2020, 6, 1).and_hms(12, 30, 0)));
+    assert_eq!(serde_json::to_value(notification).unwrap()["type"], 10);
+
+    let notifications = Notification::parse(&format!("[{},{}]", text, r#"{"uid":"3c4d","ts":0,"type":12}"#)).unwrap();
+    assert_eq!(notifications[1].r#type, NotificationType::Unknown(12));
+    assert_eq!(notifications[1].level(), None);
+    assert!(Notification::parse(" ").unwrap().is_empty());
+    assert!(Notification::parse("{").is_err());
+}
+
+#[test]
+fn message_center_entries() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let text = r#"{"entries":[
+        {"entryUuid":"a1","eventId":101,"severity":3,"title":"Extension offline","desc":"No connection","affectedUuids":["30000000-0000-0001-ffff000000000000","unknown"],"roomUuid":"10000000-0000-0001-ffff000000000000","timestamps":[360201600,360246600],"isHistoric":false},
+        {"entryUuid":"b2","eventId":102,"severity":1,"title":"Update available","isHistoric":false,"confirmedAt":360201600},
+        {"entryUuid":"c3","eventId":103,"severity":9,"title":"Resolved","isHistoric":true}
+    ]}"#;
+    let message_center = MessageCenter::parse(text).unwrap();
+    assert_eq!(message_center.active().count(), 2);
+    let unconfirmed: Vec<_> = message_center.unconfirmed().map(|entry| entry.entry_uuid.as_str()).collect();
+    assert_eq!(unconfirmed, vec!["a1"]);
+
+    let entry = message_center.get("a1").unwrap();
+    assert_eq!(entry.severity, MessageSeverity::Error);
+    assert_eq!(entry.description, "No connection");
+    assert_eq!(entry.room(&loxapp3).map(|room| room.uuid.as_str()), Some("10000000-0000-0001-ffff000000000000"));
+    let controls: Vec<_> = entry.affected_controls(&loxapp3).into_iter().map(|control| control.name.as_str()).collect();
+    assert_eq!(controls, vec!["Ceiling Light"]);
+    let utc = FixedOffset::east(0);
+    assert_eq!(entry.last_occurrence(&utc), Some(utc.ymd(2020, 6, 1).and_hms(12, 30, 0)));
+    assert_eq!(message_center.get("c3").unwrap().severity, MessageSeverity::Unknown(9));
+
+    let entries = MessageCenter::parse(r#"[{"entryUuid":"d4","eventId":1,"severity":2}]"#).unwrap();
+    assert_eq!(entries.entries[0].severity, MessageSeverity::Warning);
+    assert!(loxapp3.system_status().is_none());
+
+    let mut json: serde_json::Value = serde_json::from_str(LOXAPP3).unwrap();
+    json["messageCenter"] = serde_json::json!({
+        "50000000-0000-0001-ffff000000000000": {"name": "Alerts", "uuidAction": "50000000-0000-0001-ffff000000000000", "states": {}},
+    });
+    let loxapp3: LoxoneApp3 = serde_json::from_value(json.clone()).unwrap();
+    assert!(loxapp3.system_status().is_none());
+    json["messageCenter"]["50000000-0000-0002-ffff000000000000"] = serde_json::json!({"name": "SystemStatus", "uuidAction": "50000000-0000-0002-ffff000000000000", "states": {}});
+    let loxapp3: LoxoneApp3 = serde_json::from_value(json).unwrap();
+    assert_eq!(loxapp3.system_status().unwrap().uuid_action, "50000000-0000-0002-ffff000000000000");
+
+    assert_eq!(MessageCenterCommand::Acknowledge("a1".to_owned()).mutation(), "markAsRead/a1");
+    assert_eq!(MessageCenterCommand::Confirm("a1".to_owned()).mutation(), "confirm/a1");
+}
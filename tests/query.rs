@@ -0,0 +1,43 @@
+use loxone::loxapp3::controllers::{Dimmer, LightControllerV2, Switch};
+use loxone::loxapp3::LoxoneApp3;
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+
+fn names<T>(controls: loxone::loxapp3::query::LoxoneQuery<'_, T>) -> Vec<&str> {
+    controls.into_iter().map(|control| control.name).collect()
+}
+
+#[test]
+fn query_includes_sub_controls() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    assert_eq!(names(loxapp3.controls()), ["Blinds", "Broken Switch", "Ceiling Light", "Floor Lamp", "Lighting Controller", "Presence", "Spots"]);
+    assert_eq!(names(loxapp3.controls().top_level()).len(), 5);
+
+    let lamp = loxapp3.controls().named("floor lamp").first().unwrap();
+    assert_eq!(lamp.parent.map(String::as_str), Some("30000000-0000-0005-ffff000000000000"));
+    assert_eq!(lamp.room.unwrap().name, "Living Room");
+    assert_eq!(lamp.cat.unwrap().name, "Lighting");
+}
+
+#[test]
+fn query_by_room_category_and_type() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    assert_eq!(names(loxapp3.controls().in_room("kitchen")), ["Broken Switch", "Ceiling Light"]);
+    assert_eq!(names(loxapp3.controls().in_room("10000000-0000-0002-ffff000000000000").in_category("Lighting")), ["Floor Lamp", "Lighting Controller", "Spots"]);
+    assert_eq!(names(loxapp3.controls().of_type::<Dimmer>()), ["Ceiling Light", "Floor Lamp"]);
+    assert_eq!(names(loxapp3.controls().in_room("Kitchen").of_type::<Dimmer>().favorites()), ["Ceiling Light"]);
+    // The broken switch has no states and is kept as an unknown controller.
+    assert_eq!(names(loxapp3.controls().of_type::<Switch>()), ["Spots"]);
+
+    let controller = loxapp3.controls().of_type::<LightControllerV2>().first().unwrap();
+    assert_eq!(controller.data.sub_controls.len(), 2);
+}
+
+#[test]
+fn query_fuzzy_search() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    assert_eq!(names(loxapp3.controls().search("light")), ["Lighting Controller", "Ceiling Light"]);
+    assert_eq!(names(loxapp3.controls().search("Blnds")), ["Blinds"]);
+    assert_eq!(names(loxapp3.controls().search("ceiling lihgt")), ["Ceiling Light"]);
+    assert!(loxapp3.controls().search("garage").is_empty());
+}
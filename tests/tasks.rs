@@ -0,0 +1,62 @@
+use chrono::{FixedOffset, TimeZone};
+use loxone::loxapp3::calendar::{self, CalendarEntry, CalendarMode};
+use loxone::loxapp3::tasks::Task;
+use loxone::loxapp3::LoxoneApp3;
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+
+#[test]
+fn operating_modes() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let names: Vec<_> = loxapp3.operating_mode_list().into_iter().map(|mode| mode.name).collect();
+    assert_eq!(names, vec!["Automatic", "Holiday", "Non-Working Day"]);
+
+    assert_eq!(calendar::parse_operating_modes("[0, 3]"), vec![0, 3]);
+    assert_eq!(calendar::parse_operating_modes("1"), vec![1]);
+    assert!(calendar::parse_operating_modes("").is_empty());
+    let active: Vec<_> = loxapp3.active_operating_modes("3,2,1").into_iter().map(|mode| (mode.id, mode.name)).collect();
+    assert_eq!(active, vec![(3, "Non-Working Day"), (1, "Holiday")]);
+}
+
+#[test]
+fn calendar_entries() {
+    let text = r#"[
+        {"uuid":"0b734138-0234-2c7d-ffff403fb0c34b9e","name":"Christmas","operatingMode":1,"calMode":0,"startDate":"2020-12-24","startHour":0,"startMinutes":0,"endHour":0,"endMinutes":0},
+        {"uuid":"0b734138-0234-2c7e-ffff403fb0c34b9e","name":"Easter Monday","operatingMode":3,"calMode":1,"startDate":"2020-01-01","eastern":1}
+    ]"#;
+    let entries = CalendarEntry::parse(text).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].cal_mode, CalendarMode::Yearly);
+    assert_eq!(entries[0].update_cmd().unwrap(), "jdev/sps/calendarupdateentry/0b734138-0234-2c7d-ffff403fb0c34b9e/Christmas/1/0/2020-12-24/0/0/0/0");
+    assert_eq!(entries[1].create_cmd().unwrap(), "jdev/sps/calendarcreateentry/Easter%20Monday/3/1/2020-01-01/0/0/0/0/1");
+    assert_eq!(CalendarEntry::delete_cmd(&entries[1].uuid), "jdev/sps/calendardeleteentry/0b734138-0234-2c7e-ffff403fb0c34b9e");
+    assert!(CalendarEntry::parse("").unwrap().is_empty());
+}
+
+#[test]
+fn calendar_entries_require_a_start_date() {
+    assert!(CalendarEntry::parse(r#"[{"name":"Easter Monday","operatingMode":3,"calMode":1,"eastern":1}]"#).is_err());
+
+    let mut entry = CalendarEntry::parse(r#"[{"name":"Holidays","operatingMode":2,"calMode":2,"startDate":"2020-07-01"}]"#).unwrap().remove(0);
+    assert_eq!(entry.create_cmd().unwrap(), "jdev/sps/calendarcreateentry/Holidays/2/2/2020-07-01/0/0/0/0");
+    for start_date in &["", "2020-13-01", "01.07.2020"] {
+        entry.start_date = start_date.to_string();
+        assert!(entry.create_cmd().is_err(), "{:?}", start_date);
+        assert!(entry.update_cmd().is_err(), "{:?}", start_date);
+    }
+}
+
+#[test]
+fn planned_tasks() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let text = r#"[{"uuid":"0b734138-0234-2c7f-ffff403fb0c34b9e","name":"Lights off","controlUuid":"30000000-0000-0001-ffff000000000000","command":"off","time":360246600,"user":"admin"}]"#;
+    let tasks = Task::parse(text).unwrap();
+    assert_eq!(tasks[0].control(&loxapp3).map(|control| control.name.as_str()), Some("Ceiling Light"));
+    let cet = FixedOffset::east(3600);
+    assert_eq!(tasks[0].time(&cet), Some(cet.ymd(2020, 6, 1).and_hms(12, 30, 0)));
+    assert!(Task::parse(" ").unwrap().is_empty());
+
+    let timestamp = loxone::time::from_datetime(&cet.ymd(2020, 6, 1).and_hms(12, 30, 0), &cet);
+    assert_eq!(Task::create_cmd(&tasks[0].control_uuid.clone().unwrap(), &tasks[0].command, timestamp), "jdev/sps/addcmd/30000000-0000-0001-ffff000000000000/360246600/off");
+    assert_eq!(Task::delete_cmd(&tasks[0].uuid), "jdev/sps/deletecmd/0b734138-0234-2c7f-ffff403fb0c34b9e");
+}
@@ -0,0 +1,42 @@
+use chrono::{Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
+use loxone::time::{self, MiniserverTime};
+
+#[test]
+fn convert_timestamps() {
+    assert_eq!(time::to_naive(0), Some(NaiveDate::from_ymd(2009, 1, 1).and_hms(0, 0, 0)));
+    assert_eq!(time::to_naive(360_201_600 + 3661), Some(NaiveDate::from_ymd(2020, 6, 1).and_hms(1, 1, 1)));
+    assert_eq!(time::to_naive(i64::MAX), None);
+    assert_eq!(time::from_naive(&NaiveDate::from_ymd(2008, 12, 31).and_hms(23, 0, 0)), -3600);
+
+    let cest = FixedOffset::east(2 * 3600);
+    let datetime = time::to_datetime(360_201_600, &cest).unwrap();
+    assert_eq!(datetime, cest.ymd(2020, 6, 1).and_hms(0, 0, 0));
+    assert_eq!(datetime.with_timezone(&Utc), Utc.ymd(2020, 5, 31).and_hms(22, 0, 0));
+    assert_eq!(time::from_datetime(&Utc.ymd(2020, 5, 31).and_hms(22, 0, 0), &cest), 360_201_600);
+    assert_eq!(time::from_datetime(&datetime, &Utc), 360_201_600 - 2 * 3600);
+}
+
+#[test]
+fn parse_timezone_offsets() {
+    assert_eq!(time::parse_timezone_offset("60"), Some(FixedOffset::east(3600)));
+    assert_eq!(time::parse_timezone_offset("+02:00"), Some(FixedOffset::east(7200)));
+    assert_eq!(time::parse_timezone_offset("-03:30"), Some(FixedOffset::west(12600)));
+    assert_eq!(time::parse_timezone_offset("-00:30"), Some(FixedOffset::west(1800)));
+    assert_eq!(time::parse_timezone_offset("CET"), None);
+    assert_eq!(time::parse_timezone_offset("100000"), None);
+}
+
+#[test]
+fn miniserver_time_drift() {
+    let cet = FixedOffset::east(3600);
+    let now = Utc.ymd(2020, 6, 1).and_hms(12, 0, 0);
+    let time = MiniserverTime::new(NaiveTime::from_hms(13, 0, 5), cet, now);
+    assert_eq!(time.time, cet.ymd(2020, 6, 1).and_hms(13, 0, 5));
+    assert_eq!(time.drift, Duration::seconds(5));
+
+    // The Miniserver clock lagging behind across midnight.
+    let now = Utc.ymd(2020, 6, 1).and_hms(23, 0, 2);
+    let time = MiniserverTime::new(NaiveTime::from_hms(23, 59, 58), cet, now);
+    assert_eq!(time.time, cet.ymd(2020, 6, 1).and_hms(23, 59, 58));
+    assert_eq!(time.drift, Duration::seconds(-4));
+}
@@ -0,0 +1,41 @@
+use loxone::loxapp3::LoxoneApp3;
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+
+#[test]
+fn walk_visits_sub_controls_depth_first() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let names: Vec<_> = loxapp3.walk().map(|node| (node.name, node.depth())).collect();
+    assert_eq!(names, [
+        ("Blinds", 0),
+        ("Broken Switch", 0),
+        ("Ceiling Light", 0),
+        ("Lighting Controller", 0),
+        ("Floor Lamp", 1),
+        ("Spots", 1),
+        ("Presence", 0),
+    ]);
+}
+
+#[test]
+fn sub_controls_inherit_room_and_category() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let spots = loxapp3.walk().find(|node| node.name == "Spots").unwrap();
+    assert_eq!(spots.path, ["30000000-0000-0005-ffff000000000000"]);
+    assert_eq!(spots.room.map(String::as_str), Some("10000000-0000-0002-ffff000000000000"));
+    assert_eq!(spots.cat.map(String::as_str), Some("20000000-0000-0001-ffff000000000000"));
+}
+
+#[test]
+fn tree_links_parents_and_children() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let tree = loxapp3.tree();
+    assert_eq!(tree.len(), 7);
+    assert_eq!(tree.roots().count(), 5);
+
+    let children: Vec<_> = tree.children("30000000-0000-0005-ffff000000000000").map(|node| node.name).collect();
+    assert_eq!(children, ["Floor Lamp", "Spots"]);
+    assert_eq!(tree.parent("30000000-0000-0006-ffff000000000000").unwrap().name, "Lighting Controller");
+    assert!(tree.parent("30000000-0000-0005-ffff000000000000").is_none());
+    assert_eq!(tree.children("30000000-0000-0001-ffff000000000000").count(), 0);
+}
@@ -0,0 +1,40 @@
+use loxone::loxapp3::{LoxoneApp3, LoxoneState};
+use loxone::StructureWatcher;
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+const MODIFICATIONS: &str = "0f000000-0000-000c-ffff000000000000";
+
+fn watcher() -> StructureWatcher {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    StructureWatcher::new(loxapp3, None).0
+}
+
+#[test]
+fn first_modifications_value_is_only_recorded() {
+    let mut watcher = watcher();
+    assert!(!watcher.observe(&MODIFICATIONS.to_owned(), &LoxoneState::Value(1.0)));
+}
+
+#[test]
+fn unchanged_modifications_value_is_ignored() {
+    let mut watcher = watcher();
+    watcher.observe(&MODIFICATIONS.to_owned(), &LoxoneState::Value(1.0));
+    assert!(!watcher.observe(&MODIFICATIONS.to_owned(), &LoxoneState::Value(1.0)));
+}
+
+#[test]
+fn changed_modifications_value_triggers_reload() {
+    let mut watcher = watcher();
+    watcher.observe(&MODIFICATIONS.to_owned(), &LoxoneState::Value(1.0));
+    assert!(watcher.observe(&MODIFICATIONS.to_owned(), &LoxoneState::Value(2.0)));
+    assert!(!watcher.observe(&MODIFICATIONS.to_owned(), &LoxoneState::Value(2.0)));
+}
+
+#[test]
+fn other_states_are_ignored() {
+    let mut watcher = watcher();
+    let position = "30000000-0000-0001-ffff000000000001".to_owned();
+    assert!(!watcher.observe(&position, &LoxoneState::Value(1.0)));
+    assert!(!watcher.observe(&position, &LoxoneState::Value(2.0)));
+    assert!(!watcher.observe(&MODIFICATIONS.to_owned(), &LoxoneState::Value(2.0)));
+}
@@ -0,0 +1,83 @@
+use chrono::{FixedOffset, TimeZone, Utc};
+use loxone::loxapp3::weather::{TemperatureUnit, WeatherField, WeatherForecast, WeatherType};
+use loxone::loxapp3::{LoxoneApp3, LoxoneWeatherEntry};
+
+const LOXAPP3: &str = include_str!("fixtures/LoxAPP3.json");
+
+/// Seconds from 2009-01-01 to 2020-06-01.
+const JUNE_2020: i32 = 360_201_600;
+
+fn entry(hour: i32, weather_type: i32, temperature: f64, precipitation: f64) -> LoxoneWeatherEntry {
+    LoxoneWeatherEntry {
+        timestamp: JUNE_2020 + hour * 3600,
+        weather_type,
+        wind_direction: 180,
+        solar_radiation: 0,
+        relative_humidity: 60,
+        temperature,
+        perceived_temperature: temperature - 1.0,
+        dew_point: 10.0,
+        precipitation,
+        wind_speed: f64::from(hour),
+        barometric_pressure: 1013.0,
+    }
+}
+
+fn entries() -> Vec<LoxoneWeatherEntry> {
+    vec![entry(23, 11, 14.0, 0.5), entry(0, 1, 12.0, 0.0), entry(1, 2, 10.0, 0.0), entry(22, 16, 18.0, 1.5), entry(24, 7, 13.0, 0.0)]
+}
+
+#[test]
+fn forecast_entries() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let forecast = loxapp3.weather_forecast(&entries(), &Utc);
+    assert_eq!(forecast.temperature_unit(), TemperatureUnit::Celsius);
+    assert_eq!(forecast.entries().len(), 5);
+    assert_eq!(forecast.entries()[0].time, Utc.ymd(2020, 6, 1).and_hms(0, 0, 0));
+    assert_eq!(forecast.entries()[0].weather_type, WeatherType::Clear);
+
+    let now = Utc.ymd(2020, 6, 1).and_hms(0, 30, 0);
+    assert_eq!(forecast.current(now).map(|entry| entry.temperature), Some(12.0));
+    let next: Vec<_> = forecast.next_hours(now, 2).map(|entry| entry.temperature).collect();
+    assert_eq!(next, vec![12.0, 10.0]);
+
+    let days = forecast.daily(&Utc);
+    assert_eq!(days.len(), 2);
+    assert_eq!((days[0].min_temperature, days[0].max_temperature, days[0].precipitation), (10.0, 18.0, 2.0));
+    assert_eq!(days[1].date, Utc.ymd(2020, 6, 2).naive_utc());
+    let days = forecast.daily(&FixedOffset::east(2 * 3600));
+    assert_eq!(days.len(), 2);
+    assert_eq!((days[1].min_temperature, days[1].max_temperature), (13.0, 18.0));
+}
+
+#[test]
+fn weather_types_and_fields() {
+    let loxapp3: LoxoneApp3 = serde_json::from_str(LOXAPP3).unwrap();
+    let forecast = loxapp3.weather_forecast(&[], &Utc);
+    assert_eq!(forecast.text(WeatherType::Clear), "Clear");
+    assert_eq!(forecast.text(WeatherType::Rain), "Rain");
+    assert_eq!(WeatherType::from(16).icon(), "light-rain");
+    assert_eq!(WeatherType::from(99), WeatherType::Unknown(99));
+    assert_eq!(i32::from(WeatherType::HeavySleetShowers), 29);
+
+    let fields: Vec<_> = forecast.fields().into_iter().map(|(field, _)| field).collect();
+    assert_eq!(fields, vec![WeatherField::Temperature, WeatherField::RelativeHumidity]);
+    assert_eq!(WeatherField::from_name("Barometric pressure"), Some(WeatherField::BarometricPressure));
+    assert_eq!(forecast.unit(WeatherField::Temperature), "°C");
+    assert_eq!(forecast.unit(WeatherField::RelativeHumidity), "%");
+    assert_eq!(forecast.unit(WeatherField::WindSpeed), "km/h");
+    assert_eq!(forecast.format(WeatherField::Temperature), Some("%.1f°"));
+    assert_eq!(forecast.format(WeatherField::BarometricPressure), Some("%.0fhPa"));
+    assert_eq!(forecast.format(WeatherField::DewPoint), None);
+}
+
+#[test]
+fn fahrenheit_conversion() {
+    let forecast = WeatherForecast::new(&entries(), TemperatureUnit::from(1), &FixedOffset::east(3600), None);
+    let entry = forecast.entries()[0];
+    assert_eq!(entry.time, Utc.ymd(2020, 5, 31).and_hms(23, 0, 0));
+    assert_eq!((entry.temperature, entry.perceived_temperature, entry.dew_point), (53.6, 51.8, 50.0));
+    assert_eq!(entry.get(WeatherField::BarometricPressure), 1013.0);
+    assert_eq!(forecast.unit(WeatherField::DewPoint), "°F");
+    assert_eq!(forecast.text(WeatherType::HighFog), "High fog");
+}